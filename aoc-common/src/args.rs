@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+use std::env;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::{self, Error, Result};
+
+/// Command-line arguments, consumed option by option.
+///
+/// Options may be given as `--name value` or `--name=value`. Every argument
+/// must be taken by the caller before `finish` is called, so that typos are
+/// reported instead of silently ignored.
+pub struct Args {
+    program: String,
+    args: VecDeque<String>,
+}
+
+impl Args {
+    pub fn from_env() -> Self {
+        let mut args = env::args();
+        let program = args.next().unwrap_or_default();
+        Self::new(program, args)
+    }
+
+    pub fn new(program: impl Into<String>, args: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            program: program.into(),
+            args: args.into_iter().collect(),
+        }
+    }
+
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Takes the switch `--name`, returning whether it was present.
+    pub fn flag(&mut self, name: &str) -> bool {
+        let flag = format!("--{}", name);
+        if let Some(i) = self.args.iter().position(|arg| *arg == flag) {
+            self.args.remove(i);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Takes the option `--name` and parses its value. A value that can't be
+    /// parsed is a usage error, explained by `T`'s own parse error.
+    pub fn value<T: FromStr>(&mut self, name: &str) -> Result<Option<T>>
+    where
+        T::Err: Display,
+    {
+        let parse = |value: &str| {
+            value
+                .parse::<T>()
+                .map_err(|e| Error::Usage(format!("--{}: {}", name, e)))
+        };
+        let flag = format!("--{}", name);
+        let prefix = format!("--{}=", name);
+        for i in 0..self.args.len() {
+            if self.args[i] == flag {
+                self.args.remove(i);
+                return match self.args.remove(i) {
+                    Some(value) => Ok(Some(parse(&value)?)),
+                    None => Err(Error::Usage(format!("{} requires a value", flag))),
+                };
+            }
+            if let Some(value) = self.args[i].strip_prefix(&prefix) {
+                let value = parse(value)?;
+                self.args.remove(i);
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Takes every `--name` option, which may be given more than once, and
+    /// parses their values.
+    pub fn values<T: FromStr>(&mut self, name: &str) -> Result<Vec<T>>
+    where
+        T::Err: Display,
+    {
+        let mut values = Vec::new();
+        while let Some(value) = self.value(name)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    /// Takes the next argument that is not an option.
+    pub fn positional<T: FromStr>(&mut self, name: &str) -> Result<Option<T>> {
+        match self.args.iter().position(|arg| !arg.starts_with("--")) {
+            Some(i) => {
+                let value = self.args.remove(i).unwrap();
+                Ok(Some(error::parse(&value, name)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Fails if any argument was not taken.
+    pub fn finish(self) -> Result<()> {
+        match self.args.front() {
+            Some(arg) => Err(Error::Usage(format!("unexpected argument `{}`", arg))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new("aoc", args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn takes_flags_values_and_positionals() {
+        let mut args = args(&["run", "--all", "--part", "2", "--input=in.txt"]);
+        assert!(args.flag("all"));
+        assert_eq!(args.value::<u8>("part").unwrap(), Some(2));
+        assert_eq!(
+            args.value::<String>("input").unwrap(),
+            Some("in.txt".to_string())
+        );
+        assert_eq!(
+            args.positional::<String>("a command").unwrap(),
+            Some("run".to_string())
+        );
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn takes_repeated_values() {
+        let mut args = args(&["--day", "1", "x", "--day=3"]);
+        assert_eq!(args.values::<u8>("day").unwrap(), vec![1, 3]);
+        assert_eq!(args.values::<u8>("day").unwrap(), vec![]);
+        assert_eq!(
+            args.positional::<String>("a command").unwrap(),
+            Some("x".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_options_are_none() {
+        let mut args = args(&[]);
+        assert!(!args.flag("all"));
+        assert_eq!(args.value::<u8>("part").unwrap(), None);
+        assert_eq!(args.positional::<u8>("a day").unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        assert!(args(&["--part"]).value::<u8>("part").is_err());
+        assert!(args(&["--part", "x"]).value::<u8>("part").is_err());
+    }
+
+    #[test]
+    fn bad_values_are_usage_errors_explained_by_the_type() {
+        let error = args(&["--part=x"])
+            .value::<crate::Part>("part")
+            .unwrap_err();
+        assert!(matches!(error, Error::Usage(_)));
+        assert_eq!(
+            error.to_string(),
+            "--part: expected a part (1 or 2), found `x`"
+        );
+    }
+
+    #[test]
+    fn finish_rejects_leftovers() {
+        assert!(args(&["--bogus"]).finish().is_err());
+    }
+}
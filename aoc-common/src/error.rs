@@ -0,0 +1,230 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::result;
+use std::str::FromStr;
+
+/// Anything that can go wrong while reading or solving a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Open(PathBuf, io::Error),
+    Parse(ParseError),
+    Puzzle(Box<dyn error::Error + Send + Sync>),
+    Usage(String),
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl Error {
+    /// Wraps an error raised by a day's own puzzle logic.
+    pub fn puzzle(error: impl error::Error + Send + Sync + 'static) -> Self {
+        Error::Puzzle(Box::new(error))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(io_error) => write!(f, "{}", io_error),
+            Self::Open(path, io_error) => write!(f, "cannot read {}: {}", path.display(), io_error),
+            Self::Parse(parse_error) => write!(f, "{}", parse_error),
+            Self::Puzzle(puzzle_error) => write!(f, "{}", puzzle_error),
+            Self::Usage(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(io_error) => Some(io_error),
+            Self::Open(_, io_error) => Some(io_error),
+            Self::Parse(parse_error) => Some(parse_error),
+            Self::Puzzle(puzzle_error) => Some(puzzle_error.as_ref()),
+            Self::Usage(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+/// A piece of puzzle input that is not what the parser expected.
+///
+/// Parsers that know where the input came from record the line, so that the
+/// error is displayed with the offending text pointed out:
+///
+/// ```text
+/// line 2, column 3: expected a second column (X, Y or Z), found `Q`
+///   |
+/// 2 | B Q
+///   |   ^
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    expected: String,
+    found: String,
+    /// Byte offset of `found` in the text it was parsed from.
+    offset: usize,
+    /// The number and text of the line `found` is on.
+    line: Option<(usize, String)>,
+}
+
+impl ParseError {
+    pub fn new(expected: impl Into<String>, found: impl Into<String>) -> Self {
+        Self {
+            expected: expected.into(),
+            found: found.into(),
+            offset: 0,
+            line: None,
+        }
+    }
+
+    /// Places an error raised while parsing `inner` within the larger `outer`
+    /// that `inner` was taken from.
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        self.offset += offset_of(outer, inner);
+        self
+    }
+
+    /// Places an error raised while parsing `token` on line `number` of the
+    /// input, whose text is `line`. Lines are numbered from 1.
+    pub fn at(self, number: usize, line: &str, token: &str) -> Self {
+        let mut error = self.within(line, token);
+        error.line = Some((number, line.to_string()));
+        error
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    pub fn found(&self) -> &str {
+        &self.found
+    }
+
+    /// The line number of the error, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line.as_ref().map(|(number, _)| *number)
+    }
+
+    /// The text of the line the error is on, if known.
+    pub fn line_text(&self) -> Option<&str> {
+        self.line.as_ref().map(|(_, line)| line.as_str())
+    }
+
+    /// The column, counted in characters from 1, of the error, if its line is
+    /// known.
+    pub fn column(&self) -> Option<usize> {
+        self.line.as_ref().map(|(_, line)| {
+            let before = line.get(..self.offset).unwrap_or(line);
+            before.chars().count() + 1
+        })
+    }
+}
+
+/// The byte offset of `inner` in `outer`, preferring where `inner` actually
+/// points when it is a slice of `outer`.
+fn offset_of(outer: &str, inner: &str) -> usize {
+    let outer_start = outer.as_ptr() as usize;
+    let inner_start = inner.as_ptr() as usize;
+    if inner_start >= outer_start && inner_start + inner.len() <= outer_start + outer.len() {
+        inner_start - outer_start
+    } else {
+        outer.find(inner).unwrap_or(0)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Some((number, line)), Some(column)) = (&self.line, self.column()) {
+            write!(f, "line {}, column {}: ", number, column)?;
+            self.fmt_message(f)?;
+            let gutter = " ".repeat(number.to_string().len());
+            let carets = "^".repeat(self.found.chars().count().max(1));
+            write!(f, "\n{} |", gutter)?;
+            write!(f, "\n{} | {}", number, line)?;
+            write!(f, "\n{} | {}{}", gutter, " ".repeat(column - 1), carets)
+        } else {
+            self.fmt_message(f)
+        }
+    }
+}
+
+impl ParseError {
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.found.is_empty() {
+            write!(f, "expected {}, found nothing", self.expected)
+        } else {
+            write!(f, "expected {}, found `{}`", self.expected, self.found)
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Parses `s` with `FromStr`, describing a failure as a missing `expected`.
+pub fn parse<T: FromStr>(s: &str, expected: &str) -> result::Result<T, ParseError> {
+    s.parse::<T>().map_err(|_| ParseError::new(expected, s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_without_location() {
+        let error = ParseError::new("a shape (A, B or C)", "D");
+        assert_eq!(error.to_string(), "expected a shape (A, B or C), found `D`");
+        assert_eq!(error.line(), None);
+        assert_eq!(error.column(), None);
+    }
+
+    #[test]
+    fn displays_caret_under_token() {
+        let line = "2-4,6-x";
+        let token = &line[6..];
+        let error = ParseError::new("a section number", token).at(12, line, token);
+        assert_eq!(error.line(), Some(12));
+        assert_eq!(error.line_text(), Some(line));
+        assert_eq!(error.column(), Some(7));
+        assert_eq!(
+            error.to_string(),
+            "line 12, column 7: expected a section number, found `x`\n   |\n12 | 2-4,6-x\n   |       ^"
+        );
+    }
+
+    #[test]
+    fn nests_offsets() {
+        let line = "2-4,16-x8";
+        let pair = &line[4..];
+        let number = &pair[3..];
+        let error = ParseError::new("a section number", number)
+            .within(pair, number)
+            .at(1, line, pair);
+        assert_eq!(error.column(), Some(8));
+    }
+
+    #[test]
+    fn finds_copied_tokens() {
+        let error = ParseError::new("a crate", "[a]").at(1, "[A] [a]", "[a]");
+        assert_eq!(error.column(), Some(5));
+    }
+
+    #[test]
+    fn parse_describes_failure() {
+        let error = parse::<u32>("x", "a number").unwrap_err();
+        assert_eq!(error, ParseError::new("a number", "x"));
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::iter::Enumerate;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+
+/// Directory searched for a day's input when none is named.
+pub const INPUTS_DIR: &str = "inputs";
+
+/// The conventional file name of a day's input, such as `day1.txt`.
+pub fn file_name(day: u8) -> String {
+    format!("day{}.txt", day)
+}
+
+/// Where a day's puzzle input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Decides where to read `day`'s input from.
+    ///
+    /// A named directory is searched for the day's conventional file name and
+    /// `-` names standard input. With no path, `inputs/dayN.txt` is read if it
+    /// exists and standard input otherwise.
+    pub fn resolve(path: Option<&Path>, day: u8) -> Self {
+        match path {
+            Some(path) if path == Path::new("-") => Source::Stdin,
+            Some(path) if path.is_dir() => Source::File(path.join(file_name(day))),
+            Some(path) => Source::File(path.to_path_buf()),
+            None => {
+                let path = Path::new(INPUTS_DIR).join(file_name(day));
+                if path.is_file() {
+                    Source::File(path)
+                } else {
+                    Source::Stdin
+                }
+            }
+        }
+    }
+
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+            Source::File(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(error) => Err(Error::Open(path.clone(), error)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "standard input"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Wraps a reader, hashing every byte read through it with 64-bit FNV-1a.
+pub struct Checksummed<R> {
+    inner: R,
+    hash: u64,
+}
+
+impl<R> Checksummed<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+
+    /// The checksum of the bytes read so far.
+    pub fn checksum(&self) -> u64 {
+        self.hash
+    }
+}
+
+fn fnv1a(hash: &mut u64, bytes: &[u8]) {
+    for &byte in bytes {
+        *hash ^= byte as u64;
+        *hash = hash.wrapping_mul(FNV_PRIME);
+    }
+}
+
+impl<R: Read> Read for Checksummed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        fnv1a(&mut self.hash, &buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Checksummed<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The bytes being consumed are still buffered, so refilling cannot
+        // block or fail.
+        if let Ok(buf) = self.inner.fill_buf() {
+            fnv1a(&mut self.hash, &buf[..amt]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+/// Iterates over the lines of `input` with their line endings stripped.
+pub fn lines<R: BufRead>(input: R) -> io::Lines<R> {
+    input.lines()
+}
+
+/// A run of non-blank lines, each with its line number counting from 1.
+pub type Group = Vec<(usize, String)>;
+
+/// The groups of lines in an input, separated by blank lines.
+pub struct Groups<R> {
+    lines: Enumerate<io::Lines<R>>,
+    lines_before: usize,
+}
+
+impl<R: BufRead> Iterator for Groups<R> {
+    type Item = io::Result<Group>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut group = Group::new();
+        for (i, line) in self.lines.by_ref() {
+            let mut line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if line.trim().is_empty() {
+                if group.is_empty() {
+                    continue;
+                }
+                return Some(Ok(group));
+            }
+            if line.ends_with('\r') {
+                line.pop();
+            }
+            group.push((self.lines_before + i + 1, line));
+        }
+        // The last group needn't be followed by a blank line.
+        (!group.is_empty()).then_some(Ok(group))
+    }
+}
+
+/// Iterates over the groups of lines in `input`. Lines holding nothing but
+/// whitespace separate groups, a run of them counting as one, and `\r\n` line
+/// endings are accepted.
+pub fn groups<R: BufRead>(input: R) -> Groups<R> {
+    groups_after(input, 0)
+}
+
+/// Iterates over the groups of lines in `input`, a part of some larger input
+/// that has `lines_before` lines before it.
+pub fn groups_after<R: BufRead>(input: R, lines_before: usize) -> Groups<R> {
+    Groups {
+        lines: input.lines().enumerate(),
+        lines_before,
+    }
+}
+
+/// Reads the whole of `input` into a string.
+pub fn read_to_string(mut input: impl BufRead) -> io::Result<String> {
+    let mut buf = String::new();
+    input.read_to_string(&mut buf)?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn checksums_everything_read() {
+        let mut input = Checksummed::new("a\nb\n".as_bytes());
+        let mut line = String::new();
+        input.read_line(&mut line).unwrap();
+        let mut rest = String::new();
+        input.read_to_string(&mut rest).unwrap();
+        let mut whole = Checksummed::new("a\nb\n".as_bytes());
+        io::copy(&mut whole, &mut io::sink()).unwrap();
+        assert_eq!(input.checksum(), whole.checksum());
+        assert_ne!(input.checksum(), Checksummed::new(&b""[..]).checksum());
+    }
+
+    #[test]
+    fn groups_are_separated_by_blank_lines() {
+        let groups = groups("a\nb\n\nc\n".as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            groups,
+            vec![
+                vec![(1, "a".to_string()), (2, "b".to_string())],
+                vec![(4, "c".to_string())],
+            ]
+        );
+    }
+
+    #[test]
+    fn last_group_needs_no_blank_line() {
+        let groups = groups("a\n\nb".as_bytes()).count();
+        assert_eq!(groups, 2);
+    }
+
+    #[test]
+    fn groups_after_number_lines_on() {
+        let mut groups = groups_after("\na\n".as_bytes(), 10);
+        assert_eq!(groups.next().unwrap().unwrap(), vec![(12, "a".to_string())]);
+    }
+
+    #[test]
+    fn groups_accept_crlf_and_whitespace_separators() {
+        let groups = groups("a\r\n \t\r\n\r\n\nb\r\n".as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            groups,
+            vec![vec![(1, "a".to_string())], vec![(5, "b".to_string())]]
+        );
+    }
+
+    #[test]
+    fn empty_input_checksum_is_offset_basis() {
+        assert_eq!(Checksummed::new(&b""[..]).checksum(), 0xcbf29ce484222325);
+    }
+
+    #[test]
+    fn dash_is_stdin() {
+        assert_eq!(Source::resolve(Some(Path::new("-")), 1), Source::Stdin);
+    }
+
+    #[test]
+    fn files_are_read_as_named() {
+        let path = Path::new("no/such/input.txt");
+        assert_eq!(
+            Source::resolve(Some(path), 1),
+            Source::File(path.to_path_buf())
+        );
+    }
+
+    #[test]
+    fn directories_are_searched_by_day() {
+        let dir = env::temp_dir().join(format!("aoc-common-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(
+            Source::resolve(Some(&dir), 3),
+            Source::File(dir.join("day3.txt"))
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_files_name_the_path() {
+        let source = Source::File(PathBuf::from("no/such/input.txt"));
+        match source.open() {
+            Err(Error::Open(path, _)) => assert_eq!(path, Path::new("no/such/input.txt")),
+            _ => panic!("expected an open error"),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Plumbing shared by every day's puzzle solution: reading input, reporting
+//! errors and the `Solution` trait each day implements.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+
+pub mod args;
+pub mod error;
+pub mod input;
+pub mod output;
+pub mod registry;
+pub mod solution;
+
+pub use args::Args;
+pub use error::{Error, ParseError, Result};
+pub use output::{Format, Solved};
+pub use registry::Registry;
+pub use solution::{Answers, Part, Solution};
+
+/// Runs a binary's `main`, printing any error it returns as
+/// `<program>: <error>` instead of panicking, and exiting with status 2 for a
+/// usage error or 1 for anything else.
+pub fn main(main: impl FnOnce() -> Result<()>) {
+    if let Err(error) = main() {
+        let program = env::args().next().unwrap_or_default();
+        let program = Path::new(&program)
+            .file_name()
+            .map_or(program.clone(), |name| name.to_string_lossy().into_owned());
+        eprintln!("{}: {}", program, error);
+        process::exit(match error {
+            Error::Usage(_) => 2,
+            _ => 1,
+        });
+    }
+}
+
+/// Solves `solution` and prints each answered part on its own line.
+///
+/// The input is read from the path given as the only positional argument,
+/// resolved as described by `input::Source::resolve`, and `--output json`
+/// prints the answers as JSON lines.
+pub fn run(solution: &dyn Solution) -> Result<()> {
+    let mut args = Args::from_env();
+    let usage = format!("usage: {} [--output text|json] [PATH]", args.program());
+    let format = args.value::<Format>("output")?.unwrap_or_default();
+    let path = args.positional::<PathBuf>("an input path")?;
+    args.finish().map_err(|_| Error::Usage(usage))?;
+
+    let source = input::Source::resolve(path.as_deref(), solution.day());
+    run_with(solution, &source, format)
+}
+
+/// Solves `solution` with the input read from `source` and prints each
+/// answered part in `format`.
+pub fn run_with(solution: &dyn Solution, source: &input::Source, format: Format) -> Result<()> {
+    let solved = Solved::solve(solution, source.open()?)?;
+    for report in solved.reports(&Part::ALL) {
+        match format {
+            Format::Text => println!("{}", report.answer),
+            Format::Json => println!("{}", report.to_json()),
+        }
+    }
+    Ok(())
+}
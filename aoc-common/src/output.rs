@@ -0,0 +1,154 @@
+use std::fmt::{self, Write};
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::error::{ParseError, Result};
+use crate::input::Checksummed;
+use crate::solution::{Answers, Part, Solution};
+
+/// How answers are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Plain answers, for people and shell scripts.
+    #[default]
+    Text,
+    /// One JSON object per answered part, one per line.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            invalid => Err(ParseError::new("an output format (text or json)", invalid)),
+        }
+    }
+}
+
+/// The answers from solving a day's puzzle, with the checksum of the input
+/// they were computed from and how long solving took.
+#[derive(Clone, Debug)]
+pub struct Solved {
+    pub day: u8,
+    pub answers: Answers,
+    pub checksum: u64,
+    pub elapsed: Duration,
+}
+
+impl Solved {
+    /// Solves `solution` from `input`, timing it and checksumming every byte
+    /// of the input, including any the solution left unread.
+    pub fn solve(solution: &dyn Solution, input: impl BufRead) -> Result<Self> {
+        let mut input = Checksummed::new(input);
+        let start = Instant::now();
+        let answers = solution.solve(&mut input)?;
+        let elapsed = start.elapsed();
+        io::copy(&mut input, &mut io::sink())?;
+        Ok(Self {
+            day: solution.day(),
+            answers,
+            checksum: input.checksum(),
+            elapsed,
+        })
+    }
+
+    /// Reports the answered parts among `parts`.
+    pub fn reports<'a>(&'a self, parts: &'a [Part]) -> impl Iterator<Item = Report<'a>> + 'a {
+        parts.iter().filter_map(move |&part| {
+            self.answers.get(part).map(|answer| Report {
+                day: self.day,
+                part,
+                answer,
+                checksum: self.checksum,
+                elapsed: self.elapsed,
+            })
+        })
+    }
+}
+
+/// A single answered part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Report<'a> {
+    pub day: u8,
+    pub part: Part,
+    pub answer: &'a str,
+    pub checksum: u64,
+    pub elapsed: Duration,
+}
+
+impl Report<'_> {
+    /// Renders the report as a single-line JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"input_checksum\":\"{:016x}\",\"elapsed_us\":{}}}",
+            self.day,
+            self.part,
+            json_string(self.answer),
+            self.checksum,
+            self.elapsed.as_micros()
+        )
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {} part {}: {}", self.day, self.part, self.answer)
+    }
+}
+
+/// Quotes `s` as a JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => {
+                write!(quoted, "\\u{:04x}", ch as u32).unwrap();
+            }
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_json_strings() {
+        assert_eq!(json_string("CMZ"), "\"CMZ\"");
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+
+    #[test]
+    fn renders_reports_as_json() {
+        let report = Report {
+            day: 5,
+            part: Part::Two,
+            answer: "MCD",
+            checksum: 0xcbf29ce484222325,
+            elapsed: Duration::from_micros(1500),
+        };
+        assert_eq!(
+            report.to_json(),
+            "{\"day\":5,\"part\":2,\"answer\":\"MCD\",\"input_checksum\":\"cbf29ce484222325\",\"elapsed_us\":1500}"
+        );
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use std::collections::BTreeMap;
+
+use crate::solution::Solution;
+
+/// The set of solutions known to the runner, keyed by day.
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<u8, Box<dyn Solution>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `solution`, replacing any solution already registered for its day.
+    pub fn register(&mut self, solution: Box<dyn Solution>) {
+        self.solutions.insert(solution.day(), solution);
+    }
+
+    pub fn get(&self, day: u8) -> Option<&dyn Solution> {
+        self.solutions.get(&day).map(|solution| solution.as_ref())
+    }
+
+    /// Iterates over the registered solutions in day order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Solution> {
+        self.solutions.values().map(|solution| solution.as_ref())
+    }
+}
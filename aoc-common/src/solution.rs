@@ -0,0 +1,70 @@
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::error::{ParseError, Result};
+
+/// A solver for a single day's puzzle.
+pub trait Solution {
+    /// The day of the Advent calendar this solution answers.
+    fn day(&self) -> u8;
+
+    /// Reads the puzzle from `input` and returns the answers to its parts.
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers>;
+}
+
+/// One of the two parts every puzzle is split into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            invalid => Err(ParseError::new("a part (1 or 2)", invalid)),
+        }
+    }
+}
+
+/// The printable answers to a puzzle. A part the solution does not answer is
+/// `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn new(part1: impl ToString, part2: impl ToString) -> Self {
+        Self {
+            part1: Some(part1.to_string()),
+            part2: Some(part2.to_string()),
+        }
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use aoc_common::input::{self, Source};
+use aoc_common::{Args, Error, Format, Part, Registry, Result, Solved};
+
+const USAGE: &str = "usage: aoc run <day> [--part 1|2] [--input PATH] [--output text|json]
+       aoc run --all [--part 1|2] [--input DIR] [--output text|json]";
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    day1::register(&mut registry);
+    day2::register(&mut registry);
+    day3::register(&mut registry);
+    day4::register(&mut registry);
+    day5::register(&mut registry);
+    day6::register(&mut registry);
+    day7::register(&mut registry);
+    registry
+}
+
+/// Prints every answered part among `parts`, each labelled with its day and
+/// part, or as JSON lines.
+fn print_reports(solved: &Solved, parts: &[Part], format: Format) {
+    for report in solved.reports(parts) {
+        match format {
+            Format::Text => println!("{}", report),
+            Format::Json => println!("{}", report.to_json()),
+        }
+    }
+}
+
+fn try_main() -> Result<()> {
+    let mut args = Args::from_env();
+    match args.positional::<String>("a command")?.as_deref() {
+        Some("run") => {}
+        _ => return Err(Error::Usage(USAGE.to_string())),
+    }
+    let all = args.flag("all");
+    let part = args.value::<Part>("part")?;
+    let input = args.value::<PathBuf>("input")?;
+    let format = args.value::<Format>("output")?.unwrap_or_default();
+    let day = args.positional::<u8>("a day")?;
+    args.finish()?;
+
+    let registry = registry();
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+    match (day, all) {
+        (Some(day), false) => {
+            let solution = registry
+                .get(day)
+                .ok_or_else(|| Error::Usage(format!("day {} has no solution", day)))?;
+            let source = Source::resolve(input.as_deref(), day);
+            let solved = Solved::solve(solution, source.open()?)?;
+            if let Some(part) = part {
+                let answer = solved.answers.get(part).ok_or_else(|| {
+                    Error::Usage(format!("day {} part {} is not solved yet", day, part))
+                })?;
+                // A single requested part is printed bare so scripts can capture it.
+                if format == Format::Text {
+                    println!("{}", answer);
+                    return Ok(());
+                }
+            }
+            print_reports(&solved, &parts, format);
+            Ok(())
+        }
+        (None, true) => {
+            // Every day needs its own input, so they are always read from a
+            // directory rather than standard input.
+            let dir = input.unwrap_or_else(|| PathBuf::from(input::INPUTS_DIR));
+            if !dir.is_dir() {
+                return Err(Error::Usage(format!(
+                    "{} is not a directory of inputs",
+                    dir.display()
+                )));
+            }
+            for solution in registry.iter() {
+                let source = Source::resolve(Some(&dir), solution.day());
+                let solved = Solved::solve(solution, source.open()?)?;
+                print_reports(&solved, &parts, format);
+            }
+            Ok(())
+        }
+        _ => Err(Error::Usage(USAGE.to_string())),
+    }
+}
+
+fn main() {
+    aoc_common::main(try_main)
+}
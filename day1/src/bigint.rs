@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::ParseError;
+
+/// Each limb holds nine decimal digits, which keeps parsing and printing
+/// simple.
+const BASE: u32 = 1_000_000_000;
+const DIGITS: usize = 9;
+
+/// An unsigned integer of any size, for totals too large for a `u64`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    // Least significant first, with no trailing zero limbs, so zero is empty.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn add(&self, other: &Self) -> Self {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (&self.limbs, &other.limbs)
+        } else {
+            (&other.limbs, &self.limbs)
+        };
+        let mut limbs = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0;
+        for (i, &limb) in long.iter().enumerate() {
+            let sum = limb + short.get(i).copied().unwrap_or(0) + carry;
+            limbs.push(sum % BASE);
+            carry = sum / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+        Self { limbs }
+    }
+
+    /// The nearest `f64`, or infinity if too large.
+    pub fn to_f64(&self) -> f64 {
+        self.limbs
+            .iter()
+            .rev()
+            .fold(0.0, |n, &limb| n * BASE as f64 + limb as f64)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(mut n: u64) -> Self {
+        let mut limbs = Vec::new();
+        while n > 0 {
+            limbs.push((n % BASE as u64) as u32);
+            n /= BASE as u64;
+        }
+        Self { limbs }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseError::new("a whole number", s));
+        }
+        let digits = s.trim_start_matches('0').as_bytes();
+        let limbs = digits
+            .rchunks(DIGITS)
+            .map(|chunk| chunk.iter().fold(0, |n, d| n * 10 + (d - b'0') as u32))
+            .collect();
+        Ok(Self { limbs })
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut limbs = self.limbs.iter().rev();
+        match limbs.next() {
+            Some(most) => write!(f, "{}", most)?,
+            None => return write!(f, "0"),
+        }
+        for limb in limbs {
+            write!(f, "{:0width$}", limb, width = DIGITS)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn round_trips_through_strings() {
+        for s in ["0", "7", "1000000000", "123456789012345678901234567890"] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("000042").to_string(), "42");
+    }
+
+    #[test]
+    fn adds_with_carry() {
+        let sum = big("999999999999999999").add(&big("1"));
+        assert_eq!(sum.to_string(), "1000000000000000000");
+        assert_eq!(
+            BigUint::from(u64::MAX).add(&BigUint::from(u64::MAX)),
+            big("36893488147419103230")
+        );
+    }
+
+    #[test]
+    fn orders_by_value() {
+        assert!(big("1000000000") > big("999999999"));
+        assert!(big("21") > big("12"));
+        assert_eq!(big("0"), BigUint::default());
+    }
+
+    #[test]
+    fn converts_to_f64() {
+        assert_eq!(big("12345678901").to_f64(), 12345678901.0);
+        assert_eq!(BigUint::default().to_f64(), 0.0);
+    }
+
+    #[test]
+    fn rejects_non_digits() {
+        assert!("".parse::<BigUint>().is_err());
+        assert!("-1".parse::<BigUint>().is_err());
+        assert!("1e9".parse::<BigUint>().is_err());
+    }
+}
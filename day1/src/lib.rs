@@ -0,0 +1,378 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::BufRead;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use aoc_common::error;
+use aoc_common::input::{self, Groups};
+use aoc_common::{Answers, Error, Registry, Result, Solution};
+
+pub mod bigint;
+pub mod parallel;
+pub mod report;
+pub mod stats;
+
+pub use bigint::BigUint;
+
+/// A number of calories: a `u64` unless told otherwise, or a [`BigUint`] for
+/// inputs whose totals don't fit.
+pub trait Calories: Clone + Ord + Default + fmt::Debug + fmt::Display + FromStr {
+    /// `self + other`, or `None` on overflow.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// The nearest `f64`, for statistics.
+    fn to_f64(&self) -> f64;
+}
+
+impl Calories for u64 {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl Calories for BigUint {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self.add(other))
+    }
+
+    fn to_f64(&self) -> f64 {
+        BigUint::to_f64(self)
+    }
+}
+
+/// Calories that no longer fit in the type they are counted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowError {
+    /// An elf's total overflowed on adding the item on `line`.
+    Elf { elf: usize, line: usize },
+    /// The sum of the `n` largest totals overflowed.
+    Top(usize),
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Elf { elf, line } => write!(
+                f,
+                "line {}: the calories carried by elf {} overflow",
+                line, elf
+            ),
+            Self::Top(n) => write!(f, "the calories carried by the top {} elves overflow", n),
+        }
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+impl From<OverflowError> for Error {
+    fn from(e: OverflowError) -> Self {
+        Error::puzzle(e)
+    }
+}
+
+/// One elf's inventory: where it appears in the input and a summary of the
+/// items carried.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inventory<C = u64> {
+    /// The elf's position in the input, counting from 1.
+    pub elf: usize,
+    pub items: usize,
+    pub total: C,
+    /// The fewest calories carried in one item, as `max` is the most.
+    pub min: C,
+    pub max: C,
+}
+
+impl<C: Calories> Inventory<C> {
+    fn new(elf: usize) -> Self {
+        Self {
+            elf,
+            items: 0,
+            total: C::default(),
+            min: C::default(),
+            max: C::default(),
+        }
+    }
+
+    fn add(&mut self, calories: C) -> Option<()> {
+        self.total = self.total.checked_add(&calories)?;
+        if self.items == 0 || calories < self.min {
+            self.min = calories.clone();
+        }
+        if self.items == 0 || calories > self.max {
+            self.max = calories;
+        }
+        self.items += 1;
+        Some(())
+    }
+}
+
+/// Each elf's inventory, read one elf at a time so that the inventories never
+/// have to be held in memory all at once.
+pub struct Inventories<R, C = u64> {
+    groups: Groups<R>,
+    elves: usize,
+    calories: PhantomData<C>,
+}
+
+impl<R: BufRead, C: Calories> Inventories<R, C> {
+    /// Reads inventories from `input` with one item per line and a blank line
+    /// between elves.
+    pub fn new(input: R) -> Self {
+        Self::resume(input, 0, 0)
+    }
+
+    /// Reads inventories from `input`, a part of some larger input that has
+    /// `lines_before` lines and `elves_before` elves before it.
+    pub fn resume(input: R, lines_before: usize, elves_before: usize) -> Self {
+        Self {
+            groups: input::groups_after(input, lines_before),
+            elves: elves_before,
+            calories: PhantomData,
+        }
+    }
+}
+
+impl<R: BufRead, C: Calories> Iterator for Inventories<R, C> {
+    type Item = Result<Inventory<C>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let group = match self.groups.next()? {
+            Ok(group) => group,
+            Err(e) => return Some(Err(e.into())),
+        };
+        self.elves += 1;
+        let mut inventory = Inventory::new(self.elves);
+        for (number, line) in group {
+            let calories = match error::parse::<C>(&line, "a number of calories") {
+                Ok(calories) => calories,
+                Err(e) => return Some(Err(e.at(number, &line, &line).into())),
+            };
+            if inventory.add(calories).is_none() {
+                let overflow = OverflowError::Elf {
+                    elf: self.elves,
+                    line: number,
+                };
+                return Some(Err(overflow.into()));
+            }
+        }
+        Some(Ok(inventory))
+    }
+}
+
+/// Iterates over each elf's inventory, counting calories in a `u64`.
+pub fn inventories<R: BufRead>(input: R) -> Inventories<R> {
+    Inventories::new(input)
+}
+
+/// Iterates over each elf's total.
+pub fn totals(input: impl BufRead) -> impl Iterator<Item = Result<u64>> {
+    inventories(input).map(|inventory| inventory.map(|inventory| inventory.total))
+}
+
+/// Reads the calories carried by each elf and returns each elf's total.
+pub fn parse(input: impl BufRead) -> Result<Vec<u64>> {
+    totals(input).collect()
+}
+
+/// Keeps the `n` largest totals pushed into it, using memory for no more
+/// than `n` of them.
+#[derive(Debug, Clone)]
+pub struct TopN<C = u64> {
+    n: usize,
+    // A min-heap, so the smallest of the kept totals is the one to evict.
+    heap: BinaryHeap<Reverse<C>>,
+}
+
+impl<C: Calories> TopN<C> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            heap: BinaryHeap::with_capacity(n),
+        }
+    }
+
+    pub fn push(&mut self, total: C) {
+        if self.heap.len() < self.n {
+            self.heap.push(Reverse(total));
+        } else if let Some(mut smallest) = self.heap.peek_mut() {
+            if smallest.0 < total {
+                *smallest = Reverse(total);
+            }
+        }
+    }
+
+    /// Keeps the `n` largest of the totals kept by both.
+    pub fn merge(&mut self, other: TopN<C>) {
+        for Reverse(total) in other.heap {
+            self.push(total);
+        }
+    }
+
+    /// The kept totals, largest first.
+    pub fn into_vec(self) -> Vec<C> {
+        // Sorting `Reverse`s ascending puts the largest totals first.
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(total)| total)
+            .collect()
+    }
+}
+
+/// The `n` largest of `totals`, largest first.
+pub fn top<C: Calories>(totals: impl IntoIterator<Item = C>, n: usize) -> Vec<C> {
+    let mut top = TopN::new(n);
+    totals.into_iter().for_each(|total| top.push(total));
+    top.into_vec()
+}
+
+/// The calories carried by the `n` elves carrying the most.
+pub fn top_sum<C: Calories>(totals: &[C], n: usize) -> std::result::Result<C, OverflowError> {
+    top(totals.iter().cloned(), n)
+        .iter()
+        .try_fold(C::default(), |sum, total| sum.checked_add(total))
+        .ok_or(OverflowError::Top(n))
+}
+
+/// The most calories carried by any one elf.
+pub fn part1<C: Calories>(totals: &[C]) -> C {
+    totals.iter().max().cloned().unwrap_or_default()
+}
+
+/// The calories carried by the three elves carrying the most.
+pub fn part2<C: Calories>(totals: &[C]) -> std::result::Result<C, OverflowError> {
+    top_sum(totals, 3)
+}
+
+/// The answers given the three largest totals.
+fn answers<C: Calories>(top: &[C]) -> Result<Answers> {
+    Ok(Answers::new(part1(top), part2(top)?))
+}
+
+fn solve<C: Calories>(input: &mut dyn BufRead) -> Result<Answers> {
+    let mut top = TopN::new(3);
+    for inventory in Inventories::<_, C>::new(input) {
+        top.push(inventory?.total);
+    }
+    answers(&top.into_vec())
+}
+
+/// Day 1's puzzle.
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        solve::<u64>(input)
+    }
+}
+
+/// Day 1's puzzle counting calories without limit, for stress-test inputs.
+pub struct Day1Big;
+
+impl Solution for Day1Big {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        solve::<BigUint>(input)
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Box::new(Day1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_keeps_largest_first() {
+        assert_eq!(top([5u64, 1, 9, 3, 7], 3), vec![9, 7, 5]);
+    }
+
+    #[test]
+    fn top_of_fewer_totals_than_n() {
+        assert_eq!(top([2u64, 4], 3), vec![4, 2]);
+    }
+
+    #[test]
+    fn top_zero() {
+        assert_eq!(top([2u64, 4], 0), vec![]);
+    }
+
+    #[test]
+    fn top_keeps_ties() {
+        assert_eq!(top([3u64, 3, 1, 3], 2), vec![3, 3]);
+    }
+
+    #[test]
+    fn inventories_summarise_items() {
+        let mut inventories = inventories("3\n1\n2\n\n\n4".as_bytes());
+        assert_eq!(
+            inventories.next().unwrap().unwrap(),
+            Inventory {
+                elf: 1,
+                items: 3,
+                total: 6,
+                min: 1,
+                max: 3,
+            }
+        );
+        let last = inventories.next().unwrap().unwrap();
+        assert_eq!((last.elf, last.items, last.total), (2, 1, 4));
+        assert!(inventories.next().is_none());
+    }
+
+    #[test]
+    fn totals_accept_crlf() {
+        let totals = totals("1\r\n2\r\n\r\n3\r\n".as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(totals, vec![3, 3]);
+    }
+
+    #[test]
+    fn totals_reports_bad_items() {
+        let mut totals = totals("1\n\nx\n".as_bytes());
+        assert_eq!(totals.next().unwrap().unwrap(), 1);
+        assert!(totals.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn reports_the_elf_that_overflows() {
+        let input = format!("1\n\n{}\n1\n", u64::MAX);
+        let error = parse(input.as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4: the calories carried by elf 2 overflow"
+        );
+    }
+
+    #[test]
+    fn reports_overflowing_top_sums() {
+        assert_eq!(part2(&[u64::MAX, 1]), Err(OverflowError::Top(3)));
+    }
+
+    #[test]
+    fn big_totals_do_not_overflow() {
+        let input = format!("{}\n{}\n\n1\n", u64::MAX, u64::MAX);
+        let answers = Day1Big.solve(&mut input.as_bytes()).unwrap();
+        assert_eq!(
+            answers,
+            Answers::new("36893488147419103230", "36893488147419103231")
+        );
+    }
+}
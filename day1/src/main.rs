@@ -1,26 +1,105 @@
-#![feature(binary_heap_into_iter_sorted)]
-use std::io;
-use std::collections::BinaryHeap;
-
-
-fn main() -> io::Result<()> {
-    let stdin = io::stdin();
-    let mut heap = BinaryHeap::new();
-    let mut calories = 0;
-    loop {
-        let mut buf = String::new();
-        let n = stdin.read_line(&mut buf)?;
-        match n {
-            0 => break,
-            1 => {
-                heap.push(calories);
-                calories = 0;
-                continue;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use aoc_common::input::Source;
+use aoc_common::{Args, Error, Format, Result, Solution};
+use day1::parallel::{self, Parallel};
+use day1::report::{Report, Style};
+use day1::stats::Stats;
+use day1::{BigUint, Calories, Inventories};
+
+/// What to print about the elves, and for the answers and top-N sums, how
+/// many threads to read the input on.
+enum Mode {
+    Answers(Format, Option<NonZeroUsize>),
+    Top(usize, Option<NonZeroUsize>),
+    Report(usize, Style),
+    Stats(Format),
+}
+
+fn print_report<C: Calories>(source: &Source, n: usize, style: Style) -> Result<()> {
+    let inventories = Inventories::<_, C>::new(source.open()?).collect::<Result<Vec<_>>>()?;
+    print!("{}", Report::new(inventories, n).display(style));
+    Ok(())
+}
+
+fn print_top_sum<C: Calories + Send>(
+    source: &Source,
+    n: usize,
+    threads: Option<NonZeroUsize>,
+) -> Result<()> {
+    let top = match threads {
+        Some(threads) => parallel::top::<C>(source.open()?, n, threads)?,
+        None => {
+            let mut top = day1::TopN::new(n);
+            for inventory in Inventories::<_, C>::new(source.open()?) {
+                top.push(inventory?.total);
             }
-            _ => calories += buf.trim_end().parse::<u32>().unwrap(),
+            top.into_vec()
         }
+    };
+    println!("{}", day1::top_sum(&top, n)?);
+    Ok(())
+}
+
+fn print_stats<C: Calories>(source: &Source, format: Format) -> Result<()> {
+    let totals = Inventories::<_, C>::new(source.open()?)
+        .map(|inventory| inventory.map(|inventory| inventory.total))
+        .collect::<Result<Vec<_>>>()?;
+    let stats = Stats::new(totals).ok_or_else(|| Error::Puzzle("there are no elves".into()))?;
+    match format {
+        Format::Text => print!("{}", stats),
+        Format::Json => println!("{}", stats.to_json()),
     }
-    let total = heap.into_iter_sorted().take(3).fold(0, |acc, x| acc + x);
-    println!("{}", total);
     Ok(())
 }
+
+fn run<C: Calories + Send + 'static>(
+    source: &Source,
+    mode: Mode,
+    solution: &dyn Solution,
+) -> Result<()> {
+    match mode {
+        Mode::Answers(format, Some(threads)) => {
+            aoc_common::run_with(&Parallel::<C>::new(threads), source, format)
+        }
+        Mode::Answers(format, None) => aoc_common::run_with(solution, source, format),
+        Mode::Top(n, threads) => print_top_sum::<C>(source, n, threads),
+        Mode::Report(n, style) => print_report::<C>(source, n, style),
+        Mode::Stats(format) => print_stats::<C>(source, format),
+    }
+}
+
+fn try_main() -> Result<()> {
+    let mut args = Args::from_env();
+    let usage = format!(
+        "usage: {} [--bigint] [--threads N] [--top N | --report table|csv [--top N] | --stats [--output text|json] | --output text|json] [PATH]",
+        args.program()
+    );
+    let big = args.flag("bigint");
+    let stats = args.flag("stats");
+    let threads = args.value::<NonZeroUsize>("threads")?;
+    let n = args.value::<usize>("top")?;
+    let report = args.value::<Style>("report")?;
+    let format = args.value::<Format>("output")?;
+    let path = args.positional::<PathBuf>("an input path")?;
+    args.finish().map_err(|_| Error::Usage(usage.clone()))?;
+
+    let mode = match (n, report, stats, format) {
+        (None, None, false, format) => Mode::Answers(format.unwrap_or_default(), threads),
+        (Some(n), None, false, None) => Mode::Top(n, threads),
+        (n, Some(style), false, None) if threads.is_none() => Mode::Report(n.unwrap_or(3), style),
+        (None, None, true, format) if threads.is_none() => Mode::Stats(format.unwrap_or_default()),
+        _ => return Err(Error::Usage(usage)),
+    };
+    let source = Source::resolve(path.as_deref(), day1::Day1.day());
+    if big {
+        run::<BigUint>(&source, mode, &day1::Day1Big)
+    } else {
+        run::<u64>(&source, mode, &day1::Day1)
+    }
+}
+
+fn main() {
+    aoc_common::main(try_main)
+}
@@ -0,0 +1,251 @@
+use std::io::{BufRead, Read};
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+use aoc_common::{Answers, Result, Solution};
+
+use crate::{Calories, Inventories, TopN};
+
+/// How much input is read before it is handed to a thread.
+pub const CHUNK_SIZE: usize = 4 << 20;
+
+/// Day 1's puzzle with the input split into chunks that are summed on
+/// `threads` threads.
+pub struct Parallel<C = u64> {
+    threads: NonZeroUsize,
+    calories: PhantomData<C>,
+}
+
+impl<C> Parallel<C> {
+    pub fn new(threads: NonZeroUsize) -> Self {
+        Self {
+            threads,
+            calories: PhantomData,
+        }
+    }
+}
+
+impl<C: Calories + Send> Solution for Parallel<C> {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        crate::answers(&top::<C>(input, 3, self.threads)?)
+    }
+}
+
+/// A run of whole elves' inventories cut from the input.
+struct Chunk {
+    index: usize,
+    bytes: Vec<u8>,
+}
+
+/// What a thread made of a chunk: how many elves it held and the largest of
+/// their totals, or the chunk back again if it couldn't be read.
+type Summary<C> = (usize, std::result::Result<(usize, TopN<C>), Vec<u8>>);
+
+/// The `n` largest totals in `input`, largest first, summing the elves on
+/// `threads` threads. Gives the same answers and errors as reading the
+/// inventories one by one.
+pub fn top<C: Calories + Send>(
+    input: impl BufRead,
+    n: usize,
+    threads: NonZeroUsize,
+) -> Result<Vec<C>> {
+    top_in_chunks(input, n, threads, CHUNK_SIZE)
+}
+
+fn top_in_chunks<C: Calories + Send>(
+    mut input: impl BufRead,
+    n: usize,
+    threads: NonZeroUsize,
+    chunk_size: usize,
+) -> Result<Vec<C>> {
+    // The number of lines up to the end of each chunk, for locating errors.
+    let mut lines_through = Vec::new();
+    let (chunks, jobs) = mpsc::sync_channel::<Chunk>(threads.get());
+    let jobs = Mutex::new(jobs);
+    let (summaries, results) = mpsc::channel();
+    let (read, mut summaries) = thread::scope(|scope| {
+        for _ in 0..threads.get() {
+            let (jobs, summaries) = (&jobs, summaries.clone());
+            scope.spawn(move || {
+                // The lock is only poisoned if another thread panicked.
+                while let Ok(Ok(chunk)) = jobs.lock().map(|jobs| jobs.recv()) {
+                    if summaries.send(summarise::<C>(chunk, n)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(summaries);
+        let read = split(&mut input, chunk_size, |bytes| {
+            let index = lines_through.len();
+            let lines = bytes.iter().filter(|&&b| b == b'\n').count();
+            lines_through.push(lines_through.last().copied().unwrap_or(0) + lines);
+            chunks.send(Chunk { index, bytes }).is_ok()
+        });
+        drop(chunks);
+        (read, results.iter().collect::<Vec<_>>())
+    });
+    read?;
+    summaries.sort_by_key(|(index, _)| *index);
+
+    let mut top = TopN::new(n);
+    let mut elves_before = 0;
+    for (index, summary) in summaries {
+        match summary {
+            Ok((elves, chunk_top)) => {
+                elves_before += elves;
+                top.merge(chunk_top);
+            }
+            Err(bytes) => {
+                // Read the chunk again in order to report the error just as
+                // reading the inventories one by one would.
+                let lines_before = index.checked_sub(1).map_or(0, |i| lines_through[i]);
+                for inventory in Inventories::<_, C>::resume(&bytes[..], lines_before, elves_before)
+                {
+                    inventory?;
+                }
+            }
+        }
+    }
+    Ok(top.into_vec())
+}
+
+fn summarise<C: Calories>(chunk: Chunk, n: usize) -> Summary<C> {
+    let mut top = TopN::new(n);
+    let mut elves = 0;
+    for inventory in Inventories::<_, C>::new(&chunk.bytes[..]) {
+        match inventory {
+            Ok(inventory) => top.push(inventory.total),
+            Err(_) => return (chunk.index, Err(chunk.bytes)),
+        }
+        elves += 1;
+    }
+    (chunk.index, Ok((elves, top)))
+}
+
+/// Reads `input` about `chunk_size` bytes at a time and passes `send` each run
+/// of whole groups, cut just after a blank line, until `send` returns false.
+fn split(
+    input: &mut impl BufRead,
+    chunk_size: usize,
+    mut send: impl FnMut(Vec<u8>) -> bool,
+) -> std::io::Result<()> {
+    let mut buffer = Vec::new();
+    loop {
+        let read = input
+            .by_ref()
+            .take(chunk_size as u64)
+            .read_to_end(&mut buffer)?;
+        if read == 0 {
+            if !buffer.is_empty() {
+                send(buffer);
+            }
+            return Ok(());
+        }
+        if let Some(end) = last_boundary(&buffer) {
+            let rest = buffer.split_off(end);
+            if !send(buffer) {
+                return Ok(());
+            }
+            buffer = rest;
+        }
+    }
+}
+
+/// Where the last complete blank line in `bytes` ends, if there is one.
+fn last_boundary(bytes: &[u8]) -> Option<usize> {
+    let mut end = bytes.iter().rposition(|&b| b == b'\n')?;
+    loop {
+        let start = bytes[..end]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        if bytes[start..end].iter().all(u8::is_ascii_whitespace) {
+            return Some(end + 1);
+        }
+        end = start.checked_sub(1)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BigUint;
+
+    fn threads(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    fn generate(elves: u64) -> String {
+        let mut input = String::new();
+        for elf in 0..elves {
+            for item in 0..elf % 5 + 1 {
+                input += &format!("{}\n", (elf * 7919 + item * 104729) % 100_000);
+            }
+            input += if elf % 3 == 0 { "\r\n" } else { " \n\n" };
+        }
+        input
+    }
+
+    fn sequential(input: &str, n: usize) -> Vec<u64> {
+        let mut top = TopN::new(n);
+        for inventory in crate::inventories(input.as_bytes()) {
+            top.push(inventory.unwrap().total);
+        }
+        top.into_vec()
+    }
+
+    #[test]
+    fn finds_the_last_blank_line() {
+        assert_eq!(last_boundary(b"1\n\n2\n \r\n3\n"), Some(8));
+        assert_eq!(last_boundary(b"\n1\n"), Some(1));
+        assert_eq!(last_boundary(b"1\n2\n"), None);
+        assert_eq!(last_boundary(b"1\n\n2"), Some(3));
+    }
+
+    #[test]
+    fn matches_sequential_answers() {
+        let input = generate(1000);
+        for chunk_size in [1, 7, 64, 4096] {
+            for n in [0, 1, 3, 50] {
+                let top = top_in_chunks::<u64>(input.as_bytes(), n, threads(4), chunk_size);
+                assert_eq!(top.unwrap(), sequential(&input, n));
+            }
+        }
+    }
+
+    #[test]
+    fn counts_big_totals() {
+        let input = format!("{}\n{}\n\n1\n", u64::MAX, u64::MAX);
+        let top = top_in_chunks::<BigUint>(input.as_bytes(), 1, threads(2), 3).unwrap();
+        assert_eq!(top, vec!["36893488147419103230".parse().unwrap()]);
+    }
+
+    #[test]
+    fn reports_errors_where_sequential_reading_does() {
+        let input = format!("{}\n1\n\n2\n\nx\n", generate(50));
+        let expected = crate::parse(input.as_bytes()).unwrap_err().to_string();
+        let error = top_in_chunks::<u64>(input.as_bytes(), 3, threads(3), 16).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+
+        let input = format!("{}\n{}\n1\n", generate(50), u64::MAX);
+        let expected = crate::parse(input.as_bytes()).unwrap_err().to_string();
+        let error = top_in_chunks::<u64>(input.as_bytes(), 3, threads(3), 16).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn solves_example() {
+        let example = include_str!("../tests/example.txt");
+        let answers = Parallel::<u64>::new(threads(2))
+            .solve(&mut example.as_bytes())
+            .unwrap();
+        assert_eq!(answers, Answers::new(24000, 45000));
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::ParseError;
+
+use crate::{Calories, Inventory};
+
+/// How a report is printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Style {
+    /// Aligned columns with a note on any tie at the top-N boundary.
+    #[default]
+    Table,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+impl FromStr for Style {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Style::Table),
+            "csv" => Ok(Style::Csv),
+            invalid => Err(ParseError::new("a report style (table or csv)", invalid)),
+        }
+    }
+}
+
+/// An elf's inventory with its place among the others.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row<C = u64> {
+    pub inventory: Inventory<C>,
+    /// 1 for the elf carrying the most; elves carrying the same total share
+    /// a rank and the next rank is skipped.
+    pub rank: usize,
+    /// Whether the elf counts towards the top `n`.
+    pub top: bool,
+    /// Whether the elf is tied with others for the last places in the top
+    /// `n`, with more elves tied than there are places.
+    pub tied: bool,
+}
+
+/// Every elf's inventory, in input order, ranked against the top `n`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report<C = u64> {
+    pub n: usize,
+    pub rows: Vec<Row<C>>,
+}
+
+impl<C: Calories> Report<C> {
+    pub fn new(inventories: Vec<Inventory<C>>, n: usize) -> Self {
+        let mut sorted = inventories
+            .iter()
+            .map(|i| i.total.clone())
+            .collect::<Vec<_>>();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        // The top n is ambiguous when the elf just outside it carries as
+        // much as the last elf inside it.
+        let boundary = match (n.checked_sub(1).map(|i| sorted.get(i)), sorted.get(n)) {
+            (Some(Some(last)), Some(next)) if last == next => Some(last.clone()),
+            _ => None,
+        };
+        let rows = inventories
+            .into_iter()
+            .map(|inventory| {
+                let rank = 1 + sorted.partition_point(|total| *total > inventory.total);
+                let tied = boundary.as_ref() == Some(&inventory.total);
+                Row {
+                    inventory,
+                    rank,
+                    top: rank <= n,
+                    tied,
+                }
+            })
+            .collect();
+        Self { n, rows }
+    }
+
+    /// The elves tied at the top-N boundary, in input order.
+    pub fn ties(&self) -> impl Iterator<Item = &Row<C>> {
+        self.rows.iter().filter(|row| row.tied)
+    }
+
+    /// The report in `style`.
+    pub fn display(&self, style: Style) -> impl fmt::Display + '_ {
+        Styled {
+            report: self,
+            style,
+        }
+    }
+}
+
+struct Styled<'a, C> {
+    report: &'a Report<C>,
+    style: Style,
+}
+
+const HEADERS: [&str; 7] = ["elf", "items", "total", "min", "max", "rank", "top"];
+
+impl<C: Calories> Row<C> {
+    fn cells(&self) -> [String; 7] {
+        let top = match (self.top, self.tied) {
+            (_, true) => "tie",
+            (true, false) => "yes",
+            (false, false) => "no",
+        };
+        [
+            self.inventory.elf.to_string(),
+            self.inventory.items.to_string(),
+            self.inventory.total.to_string(),
+            self.inventory.min.to_string(),
+            self.inventory.max.to_string(),
+            self.rank.to_string(),
+            top.to_string(),
+        ]
+    }
+}
+
+impl<C: Calories> fmt::Display for Styled<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Style::Csv => {
+                writeln!(f, "{}", HEADERS.join(","))?;
+                for row in &self.report.rows {
+                    writeln!(f, "{}", row.cells().join(","))?;
+                }
+                Ok(())
+            }
+            Style::Table => {
+                let rows = self.report.rows.iter().map(Row::cells).collect::<Vec<_>>();
+                let mut widths = HEADERS.map(str::len);
+                for cells in &rows {
+                    for (width, cell) in widths.iter_mut().zip(cells) {
+                        *width = (*width).max(cell.len());
+                    }
+                }
+                let headers = HEADERS.map(String::from);
+                for cells in std::iter::once(&headers).chain(&rows) {
+                    let line = cells
+                        .iter()
+                        .zip(widths)
+                        .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                        .collect::<Vec<_>>();
+                    writeln!(f, "{}", line.join("  "))?;
+                }
+                let ties = self.report.ties().collect::<Vec<_>>();
+                if let Some(first) = ties.first() {
+                    let elves = ties
+                        .iter()
+                        .map(|row| row.inventory.elf.to_string())
+                        .collect::<Vec<_>>();
+                    writeln!(
+                        f,
+                        "elves {} tie at {} calories for the last places in the top {}",
+                        elves.join(", "),
+                        first.inventory.total,
+                        self.report.n
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory(elf: usize, total: u64) -> Inventory {
+        Inventory {
+            elf,
+            items: 1,
+            total,
+            min: total,
+            max: total,
+        }
+    }
+
+    #[test]
+    fn ranks_share_ties() {
+        let report = Report::new(
+            vec![
+                inventory(1, 5),
+                inventory(2, 9),
+                inventory(3, 5),
+                inventory(4, 1),
+            ],
+            1,
+        );
+        let ranks = report.rows.iter().map(|row| row.rank).collect::<Vec<_>>();
+        assert_eq!(ranks, vec![2, 1, 2, 4]);
+        assert_eq!(report.ties().count(), 0);
+    }
+
+    #[test]
+    fn detects_ties_at_the_boundary() {
+        let report = Report::new(vec![inventory(1, 5), inventory(2, 9), inventory(3, 5)], 2);
+        let tied = report
+            .ties()
+            .map(|row| row.inventory.elf)
+            .collect::<Vec<_>>();
+        assert_eq!(tied, vec![1, 3]);
+    }
+
+    #[test]
+    fn prints_csv() {
+        let mut two = inventory(2, 7);
+        two.items = 2;
+        two.min = 3;
+        two.max = 4;
+        let report = Report::new(vec![inventory(1, 5), two], 1);
+        assert_eq!(
+            report.display(Style::Csv).to_string(),
+            "elf,items,total,min,max,rank,top\n1,1,5,5,5,2,no\n2,2,7,3,4,1,yes\n"
+        );
+    }
+
+    #[test]
+    fn prints_table_with_ties() {
+        let report = Report::new(vec![inventory(1, 5), inventory(2, 5)], 1);
+        assert_eq!(
+            report.display(Style::Table).to_string(),
+            "elf  items  total  min  max  rank  top\n  \
+             1      1      5    5    5     1  tie\n  \
+             2      1      5    5    5     1  tie\n\
+             elves 1, 2 tie at 5 calories for the last places in the top 1\n"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt;
+
+use crate::Calories;
+
+/// The percentiles reported alongside the median.
+pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// How many equal-width bins the histogram splits the totals into.
+pub const BINS: usize = 10;
+
+/// The widest bar drawn in the text histogram.
+const BAR_WIDTH: usize = 40;
+
+/// A range of totals and how many elves carried a total within it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bin {
+    pub from: f64,
+    pub to: f64,
+    pub count: usize,
+}
+
+/// How the elves' totals are distributed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats<C = u64> {
+    pub elves: usize,
+    pub min: C,
+    pub max: C,
+    pub mean: f64,
+    pub median: f64,
+    /// The population standard deviation.
+    pub stddev: f64,
+    /// Each of [`PERCENTILES`] with its value, interpolating between the
+    /// nearest totals.
+    pub percentiles: Vec<(u8, f64)>,
+    pub histogram: Vec<Bin>,
+}
+
+impl<C: Calories> Stats<C> {
+    /// Summarises `totals`, or returns `None` if there are none.
+    pub fn new(mut totals: Vec<C>) -> Option<Self> {
+        totals.sort_unstable();
+        let min = totals.first()?.clone();
+        let max = totals.last()?.clone();
+        let values = totals.iter().map(Calories::to_f64).collect::<Vec<_>>();
+        let elves = values.len();
+        let mean = values.iter().sum::<f64>() / elves as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / elves as f64;
+        Some(Self {
+            elves,
+            min,
+            max,
+            mean,
+            median: percentile(&values, 50),
+            stddev: variance.sqrt(),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|&p| (p, percentile(&values, p)))
+                .collect(),
+            histogram: histogram(&values),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(p, value)| format!("\"p{}\":{}", p, value))
+            .collect::<Vec<_>>();
+        let histogram = self
+            .histogram
+            .iter()
+            .map(|bin| {
+                format!(
+                    "{{\"from\":{},\"to\":{},\"count\":{}}}",
+                    bin.from, bin.to, bin.count
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"elves\":{},\"min\":{},\"max\":{},\"mean\":{},\"median\":{},\"stddev\":{},\"percentiles\":{{{}}},\"histogram\":[{}]}}",
+            self.elves,
+            self.min,
+            self.max,
+            self.mean,
+            self.median,
+            self.stddev,
+            percentiles.join(","),
+            histogram.join(",")
+        )
+    }
+}
+
+/// The `p`th percentile of the sorted `values`.
+fn percentile(values: &[f64], p: u8) -> f64 {
+    let rank = f64::from(p) / 100.0 * (values.len() - 1) as f64;
+    let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+    values[below] + (values[above] - values[below]) * rank.fract()
+}
+
+/// Splits the sorted `values` into [`BINS`] equal-width bins, or a single bin
+/// if they are all the same.
+fn histogram(values: &[f64]) -> Vec<Bin> {
+    let (min, max) = (values[0], values[values.len() - 1]);
+    let bins = if min == max { 1 } else { BINS };
+    let width = (max - min) / bins as f64;
+    let mut histogram = (0..bins)
+        .map(|i| Bin {
+            from: min + width * i as f64,
+            to: if i + 1 == bins {
+                max
+            } else {
+                min + width * (i + 1) as f64
+            },
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+    for value in values {
+        let i = if width == 0.0 {
+            0
+        } else {
+            (((value - min) / width) as usize).min(bins - 1)
+        };
+        histogram[i].count += 1;
+    }
+    histogram
+}
+
+impl<C: Calories> fmt::Display for Stats<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves   {}", self.elves)?;
+        writeln!(f, "min     {}", self.min)?;
+        writeln!(f, "max     {}", self.max)?;
+        writeln!(f, "mean    {:.1}", self.mean)?;
+        writeln!(f, "median  {:.1}", self.median)?;
+        writeln!(f, "stddev  {:.1}", self.stddev)?;
+        for (p, value) in &self.percentiles {
+            writeln!(f, "{:<8}{:.1}", format!("p{}", p), value)?;
+        }
+        let labels = self
+            .histogram
+            .iter()
+            .map(|bin| (format!("{:.0}", bin.from), format!("{:.0}", bin.to)))
+            .collect::<Vec<_>>();
+        let width = labels
+            .iter()
+            .map(|(from, to)| from.len().max(to.len()))
+            .max()
+            .unwrap_or(0);
+        let most = self
+            .histogram
+            .iter()
+            .map(|bin| bin.count)
+            .max()
+            .unwrap_or(0);
+        for (bin, (from, to)) in self.histogram.iter().zip(labels) {
+            let bar = bin.count * BAR_WIDTH / most.max(1);
+            writeln!(
+                f,
+                "{:>width$} - {:>width$} | {} {}",
+                from,
+                to,
+                "#".repeat(bar),
+                bin.count,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Stats {
+        Stats::new(vec![6000, 4000, 11000, 24000, 10000]).unwrap()
+    }
+
+    #[test]
+    fn summarises_example() {
+        let stats = example();
+        assert_eq!((stats.elves, stats.min, stats.max), (5, 4000, 24000));
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert!((stats.stddev - 6985.7).abs() < 0.1);
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let percentiles = example().percentiles;
+        assert_eq!(percentiles[1], (25, 6000.0));
+        assert!((percentiles[3].1 - 18800.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn histogram_covers_every_elf() {
+        let histogram = example().histogram;
+        assert_eq!(histogram.len(), BINS);
+        assert_eq!(histogram.iter().map(|bin| bin.count).sum::<usize>(), 5);
+        assert_eq!(histogram[0].count, 1);
+        assert_eq!(histogram[3].count, 2);
+        assert_eq!(histogram[BINS - 1].count, 1);
+    }
+
+    #[test]
+    fn one_bin_when_all_equal() {
+        let stats = Stats::new(vec![7u64, 7]).unwrap();
+        assert_eq!(
+            stats.histogram,
+            vec![Bin {
+                from: 7.0,
+                to: 7.0,
+                count: 2
+            }]
+        );
+        assert_eq!(stats.stddev, 0.0);
+    }
+
+    #[test]
+    fn nothing_to_summarise() {
+        assert_eq!(Stats::<u64>::new(Vec::new()), None);
+    }
+
+    #[test]
+    fn json() {
+        let stats = Stats::new(vec![7u64, 7]).unwrap();
+        assert_eq!(
+            stats.to_json(),
+            "{\"elves\":2,\"min\":7,\"max\":7,\"mean\":7,\"median\":7,\"stddev\":0,\
+             \"percentiles\":{\"p10\":7,\"p25\":7,\"p75\":7,\"p90\":7,\"p99\":7},\
+             \"histogram\":[{\"from\":7,\"to\":7,\"count\":2}]}"
+        );
+    }
+}
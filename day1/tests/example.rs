@@ -0,0 +1,27 @@
+use aoc_common::{Answers, Solution};
+
+const EXAMPLE: &str = include_str!("example.txt");
+
+#[test]
+fn parses_example() {
+    let totals = day1::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(&totals[..4], &[6000, 4000, 11000, 24000]);
+}
+
+#[test]
+fn part1_example() {
+    let totals = day1::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day1::part1(&totals), 24000);
+}
+
+#[test]
+fn part2_example() {
+    let totals = day1::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day1::part2(&totals), Ok(45000));
+}
+
+#[test]
+fn solves_example() {
+    let answers = day1::Day1.solve(&mut EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(answers, Answers::new(24000, 45000));
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+
+use crate::{
+    solve, Column, Game, Interpretation, Outcome, Round, ScoreError, Shape, StrategyGuideError,
+};
+
+/// What could be scored against the opponent's moves in a guide.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    /// The most that can be scored, playing the best shape every round.
+    pub best: u64,
+    /// The least that can be scored, playing the worst shape every round.
+    pub worst: u64,
+    pub always_win: u64,
+    pub always_draw: u64,
+    /// The score expected from following the guide, read each way, if the
+    /// opponent plays any shape at random instead of the one in the guide, or
+    /// the first round that can't be read that way.
+    pub against_random: Vec<(Interpretation, Result<f64, StrategyGuideError>)>,
+}
+
+impl Analysis {
+    pub fn new(game: &Game, guide: &[(Shape, Column)]) -> Result<Self, ScoreError> {
+        let best = total(guide, |opponent| {
+            game.shapes()
+                .map(|you| Round::new(opponent, you).score(game))
+                .max()
+                .unwrap_or(0)
+        })?;
+        let worst = total(guide, |opponent| {
+            game.shapes()
+                .map(|you| Round::new(opponent, you).score(game))
+                .min()
+                .unwrap_or(0)
+        })?;
+        let always = |outcome| {
+            total(guide, |opponent| {
+                let you = solve(game, opponent, outcome)
+                    .expect("in a valid game every shape beats and is beaten by another");
+                Round::new(opponent, you).score(game)
+            })
+        };
+        let against_random = Interpretation::ALL
+            .into_iter()
+            .map(|interpretation| {
+                (
+                    interpretation,
+                    expected_against_random(game, guide, interpretation),
+                )
+            })
+            .collect();
+        Ok(Self {
+            best,
+            worst,
+            always_win: always(Outcome::Win)?,
+            always_draw: always(Outcome::Draw)?,
+            against_random,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        let against_random = self
+            .against_random
+            .iter()
+            .map(|(interpretation, expected)| match expected {
+                Ok(expected) => format!("\"{}\":{}", interpretation, expected),
+                Err(_) => format!("\"{}\":null", interpretation),
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"best\":{},\"worst\":{},\"always_win\":{},\"always_draw\":{},\"against_random\":{{{}}}}}",
+            self.best,
+            self.worst,
+            self.always_win,
+            self.always_draw,
+            against_random.join(",")
+        )
+    }
+}
+
+/// The score expected from following the guide read as `interpretation`
+/// against an opponent playing any shape at random.
+fn expected_against_random(
+    game: &Game,
+    guide: &[(Shape, Column)],
+    interpretation: Interpretation,
+) -> Result<f64, StrategyGuideError> {
+    let mut expected = 0.0;
+    for (i, &(opponent, column)) in guide.iter().enumerate() {
+        let round = interpretation
+            .round(game, opponent, column)
+            .ok_or_else(|| StrategyGuideError::not_an_outcome(game, i + 1, opponent, column))?;
+        let scores = game
+            .shapes()
+            .map(|opponent| Round::new(opponent, round.you).score(game) as f64);
+        expected += scores.sum::<f64>() / game.shapes().count() as f64;
+    }
+    Ok(expected)
+}
+
+/// Adds up the score of each round of the guide, scored by the opponent's
+/// shape alone.
+fn total(guide: &[(Shape, Column)], score: impl Fn(Shape) -> u64) -> Result<u64, ScoreError> {
+    guide
+        .iter()
+        .enumerate()
+        .try_fold(0u64, |total, (i, &(opponent, _))| {
+            total
+                .checked_add(score(opponent))
+                .ok_or(ScoreError::Overflow { round: i + 1 })
+        })
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = vec![
+            ("best".to_string(), self.best.to_string()),
+            ("worst".to_string(), self.worst.to_string()),
+            ("always win".to_string(), self.always_win.to_string()),
+            ("always draw".to_string(), self.always_draw.to_string()),
+        ];
+        for (interpretation, expected) in &self.against_random {
+            let expected = match expected {
+                Ok(expected) => format!("{:.2}", expected),
+                Err(error) => format!(
+                    "n/a, round {} can't be read as {}",
+                    error.round(),
+                    interpretation
+                ),
+            };
+            rows.push((format!("guide by {} vs random", interpretation), expected));
+        }
+        let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        for (name, total) in rows {
+            writeln!(f, "{:<width$}  {}", name, total, width = width)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<(Shape, Column)> {
+        crate::parse(include_str!("../tests/example.txt").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn analyses_example() {
+        let analysis = Analysis::new(&Game::default(), &example()).unwrap();
+        // Against Rock, Paper and Scissors the best plays are Paper, Scissors
+        // and Rock for 8 + 9 + 7, and the worst Scissors, Rock and Paper for
+        // 3 + 1 + 2.
+        assert_eq!(analysis.best, 24);
+        assert_eq!(analysis.worst, 6);
+        assert_eq!(analysis.always_win, 24);
+        assert_eq!(analysis.always_draw, 1 + 2 + 3 + 3 * 3);
+        // The guide plays Paper, Rock and Scissors read as shapes, each
+        // scoring its own value plus 3 on average against a random opponent.
+        assert_eq!(
+            analysis.against_random,
+            vec![
+                (Interpretation::Shape, Ok(5.0 + 4.0 + 6.0)),
+                (Interpretation::Outcome, Ok(4.0 + 4.0 + 4.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn best_is_at_least_any_strategy() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let guide = crate::parse_with(&game, "D X\nE Z\nA Y\nC X\n".as_bytes()).unwrap();
+        let analysis = Analysis::new(&game, &guide).unwrap();
+        assert!(analysis.best >= analysis.always_win);
+        assert!(analysis.worst <= analysis.always_draw);
+        let shape = crate::total_score(&game, &guide, Interpretation::Shape).unwrap();
+        assert!((analysis.worst..=analysis.best).contains(&shape));
+    }
+
+    #[test]
+    fn analyses_guides_not_readable_as_outcomes() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let shapes = [Interpretation::Shape];
+        let guide = crate::parse_as(&game, &shapes, "A X\nB V\n".as_bytes()).unwrap();
+        let analysis = Analysis::new(&game, &guide).unwrap();
+        // Spock vaporizes Rock and Lizard eats Paper for the most points.
+        assert_eq!(analysis.best, 4 + 6 + 5 + 6);
+        assert!(analysis.against_random[0].1.is_ok());
+        let error = analysis.against_random[1].1.as_ref().unwrap_err();
+        assert!(matches!(error, StrategyGuideError::BadOutcome { .. }));
+        assert_eq!((error.round(), error.line()), (2, "B V"));
+        assert!(analysis
+            .to_string()
+            .contains("guide by outcome vs random  n/a, round 2 can't be read as outcome"));
+        assert!(analysis.to_json().ends_with("\"outcome\":null}}"));
+    }
+
+    #[test]
+    fn prints_json() {
+        let analysis = Analysis::new(&Game::default(), &example()).unwrap();
+        assert_eq!(
+            analysis.to_json(),
+            "{\"best\":24,\"worst\":6,\"always_win\":24,\"always_draw\":15,\
+             \"against_random\":{\"shape\":15,\"outcome\":12}}"
+        );
+    }
+}
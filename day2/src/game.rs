@@ -0,0 +1,460 @@
+use std::fmt;
+use std::io::BufRead;
+
+use aoc_common::error;
+use aoc_common::input;
+use aoc_common::{Error, ParseError, Result};
+
+use crate::{Column, Outcome, Shape};
+
+/// One of a game's shapes: what it is called, the letters standing for it in
+/// each column of the strategy guide and what playing it scores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShapeRule {
+    pub name: String,
+    pub opponent: String,
+    pub you: String,
+    pub score: u32,
+}
+
+impl ShapeRule {
+    pub fn new(name: &str, opponent: &str, you: &str, score: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            opponent: opponent.to_string(),
+            you: you.to_string(),
+            score,
+        }
+    }
+}
+
+/// What each outcome of a round scores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutcomeScores {
+    pub win: u32,
+    pub draw: u32,
+    pub loss: u32,
+}
+
+impl OutcomeScores {
+    fn get(&self, outcome: Outcome) -> &u32 {
+        match outcome {
+            Outcome::Win => &self.win,
+            Outcome::Draw => &self.draw,
+            Outcome::Loss => &self.loss,
+        }
+    }
+
+    fn get_mut(&mut self, outcome: Outcome) -> &mut u32 {
+        match outcome {
+            Outcome::Win => &mut self.win,
+            Outcome::Draw => &mut self.draw,
+            Outcome::Loss => &mut self.loss,
+        }
+    }
+}
+
+impl Default for OutcomeScores {
+    fn default() -> Self {
+        Self {
+            win: 6,
+            draw: 3,
+            loss: 0,
+        }
+    }
+}
+
+/// A hand game in which each shape beats some of the others, with how each
+/// shape and outcome is scored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    shapes: Vec<ShapeRule>,
+    /// `beats[a][b]` when shape `a` beats shape `b`.
+    beats: Vec<Vec<bool>>,
+    outcomes: OutcomeScores,
+}
+
+/// A set of shapes that doesn't make a fair game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GameError {
+    TooFewShapes,
+    /// Two shapes share a letter in the same column.
+    DuplicateLetter(String),
+    /// Only an odd number of shapes can beat each other in a cycle.
+    EvenCycle(usize),
+    /// A shape that beats itself, or two shapes that beat each other.
+    Contradiction(String, String),
+    /// A shape that beats nothing, or that nothing beats, so that some
+    /// outcomes can't be reached against it.
+    Unbeatable(String),
+    Unbeating(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewShapes => write!(f, "a game needs at least three shapes"),
+            Self::DuplicateLetter(letter) => {
+                write!(f, "more than one shape is written `{}`", letter)
+            }
+            Self::EvenCycle(n) => write!(
+                f,
+                "{} shapes can't beat each other in a cycle; say which beats which with `beats` lines",
+                n
+            ),
+            Self::Contradiction(a, b) if a == b => write!(f, "{} can't beat itself", a),
+            Self::Contradiction(a, b) => write!(f, "{} and {} can't beat each other", a, b),
+            Self::Unbeatable(name) => write!(f, "nothing beats {}", name),
+            Self::Unbeating(name) => write!(f, "{} beats nothing", name),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<GameError> for Error {
+    fn from(e: GameError) -> Self {
+        Error::puzzle(e)
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::rock_paper_scissors()
+    }
+}
+
+impl Game {
+    /// The puzzle's game.
+    pub fn rock_paper_scissors() -> Self {
+        Self::cyclic(vec![
+            ShapeRule::new("Rock", "A", "X", 1),
+            ShapeRule::new("Paper", "B", "Y", 2),
+            ShapeRule::new("Scissors", "C", "Z", 3),
+        ])
+    }
+
+    /// Rock-Paper-Scissors with Spock, written D and V, and Lizard, written E
+    /// and W.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::cyclic(vec![
+            ShapeRule::new("Rock", "A", "X", 1),
+            ShapeRule::new("Paper", "B", "Y", 2),
+            ShapeRule::new("Scissors", "C", "Z", 3),
+            ShapeRule::new("Spock", "D", "V", 4),
+            ShapeRule::new("Lizard", "E", "W", 5),
+        ])
+    }
+
+    /// A game in which each shape beats those an odd number of places before
+    /// it, wrapping around, as Paper beats Rock and Rock beats Scissors.
+    pub fn new_cyclic(shapes: Vec<ShapeRule>) -> std::result::Result<Self, GameError> {
+        if shapes.len().is_multiple_of(2) && shapes.len() >= 3 {
+            return Err(GameError::EvenCycle(shapes.len()));
+        }
+        Self::cyclic(shapes).validated()
+    }
+
+    /// A game in which the first shape of each pair beats the second.
+    pub fn new_declared(
+        shapes: Vec<ShapeRule>,
+        wins: &[(Shape, Shape)],
+    ) -> std::result::Result<Self, GameError> {
+        let n = shapes.len();
+        let mut beats = vec![vec![false; n]; n];
+        for &(Shape(a), Shape(b)) in wins {
+            if a == b || beats[b][a] {
+                let name = |i: usize| shapes[i].name.clone();
+                return Err(GameError::Contradiction(name(a), name(b)));
+            }
+            beats[a][b] = true;
+        }
+        Self {
+            shapes,
+            beats,
+            outcomes: OutcomeScores::default(),
+        }
+        .validated()
+    }
+
+    fn cyclic(shapes: Vec<ShapeRule>) -> Self {
+        let n = shapes.len();
+        let beats = (0..n)
+            .map(|a| (0..n).map(|b| (a + n - b) % n % 2 == 1).collect())
+            .collect();
+        Self {
+            shapes,
+            beats,
+            outcomes: OutcomeScores::default(),
+        }
+    }
+
+    fn validated(self) -> std::result::Result<Self, GameError> {
+        if self.shapes.len() < 3 {
+            return Err(GameError::TooFewShapes);
+        }
+        for (i, shape) in self.shapes.iter().enumerate() {
+            for other in &self.shapes[i + 1..] {
+                for (a, b) in [(&shape.opponent, &other.opponent), (&shape.you, &other.you)] {
+                    if a == b {
+                        return Err(GameError::DuplicateLetter(a.clone()));
+                    }
+                }
+            }
+        }
+        for shape in self.shapes() {
+            let name = || self.rule(shape).name.clone();
+            if !self.shapes().any(|other| self.beats(shape, other)) {
+                return Err(GameError::Unbeating(name()));
+            }
+            if !self.shapes().any(|other| self.beats(other, shape)) {
+                return Err(GameError::Unbeatable(name()));
+            }
+        }
+        Ok(self)
+    }
+
+    /// Reads a game from lines such as
+    ///
+    /// ```text
+    /// # name     opponent  you  score
+    /// shape Rock     A     X    1
+    /// beats Rock Scissors
+    /// outcome win 6
+    /// ```
+    ///
+    /// Without `beats` lines the shapes beat each other in a cycle, each
+    /// beating those an odd number of places before it. Outcomes not listed
+    /// score as in the puzzle.
+    pub fn parse(input: impl BufRead) -> Result<Self> {
+        let mut shapes = Vec::new();
+        let mut wins = Vec::new();
+        let mut outcomes = OutcomeScores::default();
+        for (i, line) in input::lines(input).enumerate() {
+            let line = line?;
+            let number = i + 1;
+            let content = line.split('#').next().unwrap_or_default();
+            let words = content.split_whitespace().collect::<Vec<_>>();
+            match words[..] {
+                [] => {}
+                ["shape", name, opponent, you, score] => {
+                    let score = error::parse::<u32>(score, "a score")
+                        .map_err(|e| e.at(number, &line, score))?;
+                    shapes.push(ShapeRule::new(name, opponent, you, score));
+                }
+                ["outcome", outcome, score] => {
+                    let parsed = outcome
+                        .parse::<Outcome>()
+                        .map_err(|e| e.at(number, &line, outcome))?;
+                    *outcomes.get_mut(parsed) = error::parse::<u32>(score, "a score")
+                        .map_err(|e| e.at(number, &line, score))?;
+                }
+                ["beats", winner, loser] => {
+                    let find = |name: &str| {
+                        shapes
+                            .iter()
+                            .position(|shape: &ShapeRule| shape.name == name)
+                            .map(Shape)
+                            .ok_or_else(|| {
+                                ParseError::new("the name of a shape listed above", name)
+                                    .at(number, &line, name)
+                            })
+                    };
+                    wins.push((find(winner)?, find(loser)?));
+                }
+                _ => {
+                    let expected = "`shape NAME OPPONENT YOU SCORE`, `beats WINNER LOSER` or `outcome OUTCOME SCORE`";
+                    let found = content.trim();
+                    return Err(ParseError::new(expected, found)
+                        .at(number, &line, found)
+                        .into());
+                }
+            }
+        }
+        let mut game = if wins.is_empty() {
+            Self::new_cyclic(shapes)
+        } else {
+            Self::new_declared(shapes, &wins)
+        }?;
+        game.outcomes = outcomes;
+        Ok(game)
+    }
+
+    /// The shape called `name`.
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.shapes().find(|&shape| self.rule(shape).name == name)
+    }
+
+    pub fn set_shape_score(&mut self, shape: Shape, score: u32) {
+        self.shapes[shape.0].score = score;
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        *self.outcomes.get(outcome)
+    }
+
+    pub fn set_outcome_score(&mut self, outcome: Outcome, score: u32) {
+        *self.outcomes.get_mut(outcome) = score;
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.shapes.len()).map(Shape)
+    }
+
+    pub fn rule(&self, shape: Shape) -> &ShapeRule {
+        &self.shapes[shape.0]
+    }
+
+    pub fn beats(&self, a: Shape, b: Shape) -> bool {
+        self.beats[a.0][b.0]
+    }
+
+    /// The opponent's shape written `letter` in the first column.
+    pub fn opponent(&self, letter: &str) -> std::result::Result<Shape, ParseError> {
+        match self
+            .shapes
+            .iter()
+            .position(|shape| shape.opponent == letter)
+        {
+            Some(i) => Ok(Shape(i)),
+            None => {
+                let letters = self.shapes.iter().map(|shape| shape.opponent.as_str());
+                let expected = format!("a shape ({})", one_of(letters));
+                Err(ParseError::new(expected, letter))
+            }
+        }
+    }
+
+    /// The second column written `letter`.
+    pub fn column(&self, letter: &str) -> std::result::Result<Column, ParseError> {
+        match self.shapes.iter().position(|shape| shape.you == letter) {
+            Some(i) => Ok(Column(i)),
+            None => {
+                let letters = self.shapes.iter().map(|shape| shape.you.as_str());
+                let expected = format!("a second column ({})", one_of(letters));
+                Err(ParseError::new(expected, letter))
+            }
+        }
+    }
+
+    /// The letters of the second column that can be read as outcomes.
+    pub fn outcome_letters(&self) -> String {
+        one_of(self.shapes.iter().take(3).map(|shape| shape.you.as_str()))
+    }
+}
+
+/// Lists `words` as `A, B or C`.
+fn one_of<'a>(words: impl Iterator<Item = &'a str>) -> String {
+    let words = words.collect::<Vec<_>>();
+    match words.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(game: &Game, name: &str) -> Shape {
+        game.shape(name).unwrap()
+    }
+
+    #[test]
+    fn rock_paper_scissors_cycle() {
+        let game = Game::rock_paper_scissors();
+        assert!(game.beats(Shape::PAPER, Shape::ROCK));
+        assert!(game.beats(Shape::ROCK, Shape::SCISSORS));
+        assert!(game.beats(Shape::SCISSORS, Shape::PAPER));
+        assert!(!game.beats(Shape::ROCK, Shape::PAPER));
+        assert!(!game.beats(Shape::ROCK, Shape::ROCK));
+    }
+
+    #[test]
+    fn lizard_spock_cycle() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        for (winner, loser) in [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ] {
+            let (winner, loser) = (named(&game, winner), named(&game, loser));
+            assert!(game.beats(winner, loser));
+            assert!(!game.beats(loser, winner));
+        }
+    }
+
+    #[test]
+    fn parses_the_lizard_spock_file() {
+        let game = Game::parse(include_str!("../games/rpsls.txt").as_bytes()).unwrap();
+        assert_eq!(game, Game::rock_paper_scissors_lizard_spock());
+    }
+
+    #[test]
+    fn parses_declared_wins() {
+        let input = "shape Rock A X 1\nshape Paper B Y 2\nshape Scissors C Z 3\n\
+                     beats Paper Rock # paper covers rock\nbeats Rock Scissors\nbeats Scissors Paper\n";
+        assert_eq!(
+            Game::parse(input.as_bytes()).unwrap(),
+            Game::rock_paper_scissors()
+        );
+    }
+
+    #[test]
+    fn rejects_unfair_games() {
+        let shapes = |n: usize| {
+            (0..n)
+                .map(|i| ShapeRule::new(&i.to_string(), &format!("o{}", i), &format!("y{}", i), 1))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(Game::new_cyclic(shapes(4)), Err(GameError::EvenCycle(4)));
+        assert_eq!(Game::new_cyclic(shapes(1)), Err(GameError::TooFewShapes));
+        assert_eq!(
+            Game::new_declared(shapes(3), &[(Shape(0), Shape(1)), (Shape(1), Shape(0))]),
+            Err(GameError::Contradiction("1".to_string(), "0".to_string()))
+        );
+        assert_eq!(
+            Game::new_declared(shapes(3), &[(Shape(0), Shape(1)), (Shape(1), Shape(2))]),
+            Err(GameError::Unbeatable("0".to_string()))
+        );
+        let mut same = shapes(3);
+        same[2].you = "y0".to_string();
+        assert_eq!(
+            Game::new_cyclic(same),
+            Err(GameError::DuplicateLetter("y0".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_outcome_scores() {
+        let input = "shape Rock A X 1\nshape Paper B Y 20\nshape Scissors C Z 300\n\
+                     outcome win 4000000000\noutcome loss 1\n";
+        let game = Game::parse(input.as_bytes()).unwrap();
+        assert_eq!(game.outcome_score(Outcome::Win), 4_000_000_000);
+        assert_eq!(game.outcome_score(Outcome::Draw), 3);
+        assert_eq!(game.outcome_score(Outcome::Loss), 1);
+        assert_eq!(game.rule(Shape::SCISSORS).score, 300);
+    }
+
+    #[test]
+    fn locates_unknown_shapes() {
+        let error = Game::parse("shape Rock A X 1\nbeats Rock Paper\n".as_bytes()).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "line 2, column 12: expected the name of a shape listed above, found `Paper`"
+        ));
+    }
+
+    #[test]
+    fn lists_letters() {
+        assert_eq!(one_of(["A"].into_iter()), "A");
+        assert_eq!(one_of(["A", "B", "C"].into_iter()), "A, B or C");
+    }
+}
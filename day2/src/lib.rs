@@ -0,0 +1,705 @@
+use std::fmt;
+use std::io::BufRead;
+use std::result;
+use std::str::FromStr;
+
+use aoc_common::input;
+use aoc_common::{Answers, Error, ParseError, Registry, Result, Solution};
+
+pub mod analysis;
+pub mod game;
+pub mod tournament;
+pub mod trace;
+
+pub use game::{Game, OutcomeScores, ShapeRule};
+
+/// One of a [`Game`]'s shapes, by its place in the game's list of shapes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Shape(pub usize);
+
+impl Shape {
+    pub const ROCK: Shape = Shape(0);
+    pub const PAPER: Shape = Shape(1);
+    pub const SCISSORS: Shape = Shape(2);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Win, Outcome::Draw, Outcome::Loss];
+}
+
+impl FromStr for Outcome {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        match s {
+            "win" => Ok(Outcome::Win),
+            "draw" => Ok(Outcome::Draw),
+            "loss" => Ok(Outcome::Loss),
+            invalid => Err(ParseError::new("an outcome (win, draw or loss)", invalid)),
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Win => write!(f, "win"),
+            Outcome::Draw => write!(f, "draw"),
+            Outcome::Loss => write!(f, "loss"),
+        }
+    }
+}
+
+/// The second column of the strategy guide, which the two parts of the puzzle
+/// read differently, by its place among the game's letters for your shapes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Column(pub usize);
+
+impl Column {
+    pub const X: Column = Column(0);
+    pub const Y: Column = Column(1);
+    pub const Z: Column = Column(2);
+
+    /// Part one reads the column as the shape you should play.
+    pub fn shape(self) -> Shape {
+        Shape(self.0)
+    }
+
+    /// Part two reads the column as how the round needs to end, which only
+    /// the first three letters can say.
+    pub fn outcome(self) -> Option<Outcome> {
+        match self {
+            Column::X => Some(Outcome::Loss),
+            Column::Y => Some(Outcome::Draw),
+            Column::Z => Some(Outcome::Win),
+            _ => None,
+        }
+    }
+}
+
+/// How to read the second column of the strategy guide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpretation {
+    /// As the shape you should play, as in part one.
+    Shape,
+    /// As how the round needs to end, as in part two.
+    Outcome,
+}
+
+impl Interpretation {
+    pub const ALL: [Interpretation; 2] = [Interpretation::Shape, Interpretation::Outcome];
+
+    /// The round played against `opponent` when `column` is read this way, or
+    /// `None` if `column` can't be read this way.
+    pub fn round(self, game: &Game, opponent: Shape, column: Column) -> Option<Round> {
+        let you = match self {
+            Interpretation::Shape => column.shape(),
+            Interpretation::Outcome => solve(game, opponent, column.outcome()?)?,
+        };
+        Some(Round::new(opponent, you))
+    }
+}
+
+impl FromStr for Interpretation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        match s {
+            "shape" => Ok(Interpretation::Shape),
+            "outcome" => Ok(Interpretation::Outcome),
+            invalid => Err(ParseError::new(
+                "an interpretation (shape or outcome)",
+                invalid,
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Interpretation::Shape => write!(f, "shape"),
+            Interpretation::Outcome => write!(f, "outcome"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: Shape,
+    pub you: Shape,
+}
+
+impl Round {
+    pub fn new(opponent: Shape, you: Shape) -> Self {
+        Round { opponent, you }
+    }
+
+    pub fn outcome(&self, game: &Game) -> Outcome {
+        if game.beats(self.you, self.opponent) {
+            Outcome::Win
+        } else if game.beats(self.opponent, self.you) {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// The score for a single round is the score for the shape you selected
+    /// (1 for Rock, 2 for Paper, and 3 for Scissors by the puzzle's rules)
+    /// plus the score for the outcome of the round
+    /// (0 if you lost, 3 if the round was a draw, and 6 if you won).
+    pub fn score(&self, game: &Game) -> u64 {
+        u64::from(game.rule(self.you).score) + u64::from(game.outcome_score(self.outcome(game)))
+    }
+}
+
+/// The shape to play against `opponent` for the round to end in `outcome`,
+/// the first in the game's list if there are several.
+pub fn solve(game: &Game, opponent: Shape, outcome: Outcome) -> Option<Shape> {
+    game.shapes()
+        .find(|&you| Round::new(opponent, you).outcome(game) == outcome)
+}
+
+/// A guide that can't be scored. Rounds are counted from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScoreError {
+    /// A round can't be read the way the guide is being scored, which
+    /// [`parse_as`] reports instead when reading a guide for that.
+    Round(Box<StrategyGuideError>),
+    /// The total no longer fits in a `u64` after the round.
+    Overflow { round: usize },
+}
+
+impl fmt::Display for ScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Round(error) => write!(f, "{}", error),
+            Self::Overflow { round } => write!(f, "round {}: the total score overflows", round),
+        }
+    }
+}
+
+impl std::error::Error for ScoreError {}
+
+impl From<ScoreError> for Error {
+    fn from(e: ScoreError) -> Self {
+        Error::puzzle(e)
+    }
+}
+
+/// A round of the strategy guide that can't be read, with the round's
+/// number, counting from 1, its line as it was read, and an error pointing at
+/// the offending text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StrategyGuideError {
+    /// The first column isn't one of the opponent's shapes.
+    BadShape {
+        round: usize,
+        line: String,
+        error: ParseError,
+    },
+    /// The second column isn't one of the game's letters for your shapes.
+    BadColumn {
+        round: usize,
+        line: String,
+        error: ParseError,
+    },
+    /// The second column can't be read as an outcome, which only the first
+    /// three letters can say.
+    BadOutcome {
+        round: usize,
+        line: String,
+        error: ParseError,
+    },
+    /// There is no space between the two columns.
+    MissingSeparator {
+        round: usize,
+        line: String,
+        error: ParseError,
+    },
+    /// Something follows the second column.
+    ExtraColumns {
+        round: usize,
+        line: String,
+        error: ParseError,
+    },
+}
+
+impl StrategyGuideError {
+    pub fn round(&self) -> usize {
+        match self {
+            Self::BadShape { round, .. }
+            | Self::BadColumn { round, .. }
+            | Self::BadOutcome { round, .. }
+            | Self::MissingSeparator { round, .. }
+            | Self::ExtraColumns { round, .. } => *round,
+        }
+    }
+
+    /// The round's line as it was read.
+    pub fn line(&self) -> &str {
+        match self {
+            Self::BadShape { line, .. }
+            | Self::BadColumn { line, .. }
+            | Self::BadOutcome { line, .. }
+            | Self::MissingSeparator { line, .. }
+            | Self::ExtraColumns { line, .. } => line,
+        }
+    }
+
+    pub fn error(&self) -> &ParseError {
+        match self {
+            Self::BadShape { error, .. }
+            | Self::BadColumn { error, .. }
+            | Self::BadOutcome { error, .. }
+            | Self::MissingSeparator { error, .. }
+            | Self::ExtraColumns { error, .. } => error,
+        }
+    }
+
+    /// A round whose second column, `column` within `line`, can't be read as
+    /// an outcome.
+    fn bad_outcome(game: &Game, round: usize, line: &str, column: &str) -> Self {
+        let expected = format!("an outcome ({})", game.outcome_letters());
+        Self::BadOutcome {
+            round,
+            line: line.to_string(),
+            error: ParseError::new(expected, column).at(round, line, column),
+        }
+    }
+
+    /// The same for a round of a guide that has already been read. Its line
+    /// is rebuilt from the two letters, which is how `parse_round` requires
+    /// it to have been written.
+    pub(crate) fn not_an_outcome(
+        game: &Game,
+        round: usize,
+        opponent: Shape,
+        column: Column,
+    ) -> Self {
+        let opponent = &game.rule(opponent).opponent;
+        let line = format!("{} {}", opponent, game.rule(column.shape()).you);
+        Self::bad_outcome(game, round, &line, &line[opponent.len() + 1..])
+    }
+}
+
+impl fmt::Display for StrategyGuideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error())
+    }
+}
+
+impl std::error::Error for StrategyGuideError {}
+
+impl From<StrategyGuideError> for Error {
+    fn from(e: StrategyGuideError) -> Self {
+        Error::puzzle(e)
+    }
+}
+
+/// Every round of a strategy guide that can't be read, in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrategyGuideErrors(pub Vec<StrategyGuideError>);
+
+impl fmt::Display for StrategyGuideErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for error in &self.0 {
+            writeln!(f, "{}", error)?;
+        }
+        match self.0.len() {
+            1 => write!(f, "1 round can't be read"),
+            n => write!(f, "{} rounds can't be read", n),
+        }
+    }
+}
+
+impl std::error::Error for StrategyGuideErrors {}
+
+impl From<StrategyGuideErrors> for Error {
+    fn from(e: StrategyGuideErrors) -> Self {
+        Error::puzzle(e)
+    }
+}
+
+/// Reads one `<opponent> <column>` round of the strategy guide for `game`,
+/// which must be readable in each of `interpretations`.
+pub fn parse_round(
+    game: &Game,
+    interpretations: &[Interpretation],
+    round: usize,
+    line: &str,
+) -> result::Result<(Shape, Column), StrategyGuideError> {
+    let (opponent_str, column_str) = line.split_once(' ').ok_or_else(|| {
+        let error = ParseError::new("a round such as `A Y`", line);
+        StrategyGuideError::MissingSeparator {
+            round,
+            line: line.to_string(),
+            error: error.at(round, line, line),
+        }
+    })?;
+    let opponent = game
+        .opponent(opponent_str)
+        .map_err(|e| StrategyGuideError::BadShape {
+            round,
+            line: line.to_string(),
+            error: e.at(round, line, opponent_str),
+        })?;
+    let (column_str, extra) = match column_str.split_once(' ') {
+        Some((column_str, extra)) => (column_str, Some(extra)),
+        None => (column_str, None),
+    };
+    let column = game
+        .column(column_str)
+        .map_err(|e| StrategyGuideError::BadColumn {
+            round,
+            line: line.to_string(),
+            error: e.at(round, line, column_str),
+        })?;
+    if interpretations.contains(&Interpretation::Outcome) && column.outcome().is_none() {
+        return Err(StrategyGuideError::bad_outcome(
+            game, round, line, column_str,
+        ));
+    }
+    if let Some(extra) = extra {
+        let error = ParseError::new("the end of the round", extra);
+        return Err(StrategyGuideError::ExtraColumns {
+            round,
+            line: line.to_string(),
+            error: error.at(round, line, extra),
+        });
+    }
+    Ok((opponent, column))
+}
+
+/// Reads the strategy guide for the puzzle's game.
+pub fn parse(input: impl BufRead) -> Result<Vec<(Shape, Column)>> {
+    parse_with(&Game::default(), input)
+}
+
+/// Reads the strategy guide for `game`, one `<opponent> <column>` round per
+/// line, to be read both ways.
+pub fn parse_with(game: &Game, input: impl BufRead) -> Result<Vec<(Shape, Column)>> {
+    parse_as(game, &Interpretation::ALL, input)
+}
+
+/// Reads the strategy guide for `game` to be read in each of
+/// `interpretations`, stopping at the first round that can't be read.
+pub fn parse_as(
+    game: &Game,
+    interpretations: &[Interpretation],
+    input: impl BufRead,
+) -> Result<Vec<(Shape, Column)>> {
+    let mut guide = Vec::new();
+    for (i, line) in input::lines(input).enumerate() {
+        guide.push(parse_round(game, interpretations, i + 1, &line?)?);
+    }
+    Ok(guide)
+}
+
+/// Reads the strategy guide like [`parse_as`], but reads every round before
+/// failing, so that all the rounds that can't be read are reported together.
+pub fn parse_keep_going(
+    game: &Game,
+    interpretations: &[Interpretation],
+    input: impl BufRead,
+) -> Result<Vec<(Shape, Column)>> {
+    let mut guide = Vec::new();
+    let mut errors = Vec::new();
+    for (i, line) in input::lines(input).enumerate() {
+        match parse_round(game, interpretations, i + 1, &line?) {
+            Ok(round) => guide.push(round),
+            Err(error) => errors.push(error),
+        }
+    }
+    if errors.is_empty() {
+        Ok(guide)
+    } else {
+        Err(StrategyGuideErrors(errors).into())
+    }
+}
+
+/// The total score from following the guide, reading the second column as
+/// `interpretation` says.
+pub fn total_score(
+    game: &Game,
+    guide: &[(Shape, Column)],
+    interpretation: Interpretation,
+) -> result::Result<u64, ScoreError> {
+    let mut total = 0u64;
+    for (i, &(opponent, column)) in guide.iter().enumerate() {
+        let round = interpretation
+            .round(game, opponent, column)
+            .ok_or_else(|| {
+                ScoreError::Round(Box::new(StrategyGuideError::not_an_outcome(
+                    game,
+                    i + 1,
+                    opponent,
+                    column,
+                )))
+            })?;
+        total = total
+            .checked_add(round.score(game))
+            .ok_or(ScoreError::Overflow { round: i + 1 })?;
+    }
+    Ok(total)
+}
+
+/// The total score when the second column is the shape you play.
+pub fn part1(guide: &[(Shape, Column)]) -> result::Result<u64, ScoreError> {
+    total_score(&Game::default(), guide, Interpretation::Shape)
+}
+
+/// The total score when the second column is how the round has to end.
+pub fn part2(guide: &[(Shape, Column)]) -> result::Result<u64, ScoreError> {
+    total_score(&Game::default(), guide, Interpretation::Outcome)
+}
+
+fn answers(game: &Game, guide: Vec<(Shape, Column)>) -> Result<Answers> {
+    let [shape, outcome] = Interpretation::ALL.map(|i| total_score(game, &guide, i));
+    Ok(Answers::new(shape?, outcome?))
+}
+
+/// Day 2's puzzle.
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        answers(&Game::default(), parse(input)?)
+    }
+}
+
+/// Day 2's puzzle played with some other game, such as Rock-Paper-Scissors-
+/// Lizard-Spock.
+pub struct Variant {
+    pub game: Game,
+    /// Whether to read the whole guide before reporting the rounds that can't
+    /// be read, rather than stopping at the first.
+    pub keep_going: bool,
+}
+
+impl Solution for Variant {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        let guide = if self.keep_going {
+            parse_keep_going(&self.game, &Interpretation::ALL, input)?
+        } else {
+            parse_with(&self.game, input)?
+        };
+        answers(&self.game, guide)
+    }
+}
+
+/// Writes `rows` under `headers`, the first column to the left and the rest
+/// to the right.
+pub(crate) fn table<const N: usize>(
+    f: &mut fmt::Formatter<'_>,
+    headers: [&str; N],
+    rows: Vec<[String; N]>,
+) -> fmt::Result {
+    let headers = headers.map(String::from);
+    let mut widths = headers.clone().map(|header| header.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    for row in std::iter::once(&headers).chain(&rows) {
+        let cells = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| match i {
+                0 => format!("{:<width$}", cell, width = width),
+                _ => format!("{:>width$}", cell, width = width),
+            })
+            .collect::<Vec<_>>();
+        writeln!(f, "{}", cells.join("  "))?;
+    }
+    Ok(())
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Box::new(Day2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_of_each_pairing() {
+        let game = Game::default();
+        assert_eq!(
+            Round::new(Shape::ROCK, Shape::PAPER).outcome(&game),
+            Outcome::Win
+        );
+        assert_eq!(
+            Round::new(Shape::PAPER, Shape::ROCK).outcome(&game),
+            Outcome::Loss
+        );
+        assert_eq!(
+            Round::new(Shape::SCISSORS, Shape::SCISSORS).outcome(&game),
+            Outcome::Draw
+        );
+    }
+
+    #[test]
+    fn score_adds_shape_and_outcome() {
+        let game = Game::default();
+        assert_eq!(Round::new(Shape::ROCK, Shape::PAPER).score(&game), 8);
+        assert_eq!(Round::new(Shape::PAPER, Shape::ROCK).score(&game), 1);
+        assert_eq!(Round::new(Shape::SCISSORS, Shape::SCISSORS).score(&game), 6);
+    }
+
+    #[test]
+    fn solve_reaches_the_requested_outcome() {
+        for game in [Game::default(), Game::rock_paper_scissors_lizard_spock()] {
+            for opponent in game.shapes() {
+                for outcome in [Outcome::Win, Outcome::Loss, Outcome::Draw] {
+                    let you = solve(&game, opponent, outcome).unwrap();
+                    assert_eq!(Round::new(opponent, you).outcome(&game), outcome);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpretations_read_the_column_differently() {
+        let game = Game::default();
+        assert_eq!(
+            Interpretation::Shape.round(&game, Shape::ROCK, Column::X),
+            Some(Round::new(Shape::ROCK, Shape::ROCK))
+        );
+        assert_eq!(
+            Interpretation::Outcome.round(&game, Shape::ROCK, Column::X),
+            Some(Round::new(Shape::ROCK, Shape::SCISSORS))
+        );
+        assert_eq!("outcome".parse(), Ok(Interpretation::Outcome));
+        assert!("both".parse::<Interpretation>().is_err());
+    }
+
+    #[test]
+    fn plays_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let input = "D W\nE Z\nA V\n";
+        let guide = parse_as(&game, &[Interpretation::Shape], input.as_bytes()).unwrap();
+        // Lizard poisons Spock, Scissors decapitate Lizard, Spock vaporizes Rock.
+        assert_eq!(
+            total_score(&game, &guide, Interpretation::Shape),
+            Ok(5 + 6 + 3 + 6 + 4 + 6)
+        );
+        // Scoring reports the same error as reading the guide for outcomes.
+        let error = parse_round(&game, &Interpretation::ALL, 1, "D W").unwrap_err();
+        assert!(matches!(error, StrategyGuideError::BadOutcome { .. }));
+        assert_eq!(
+            total_score(&game, &guide, Interpretation::Outcome),
+            Err(ScoreError::Round(Box::new(error)))
+        );
+    }
+
+    #[test]
+    fn scores_by_the_game_rules() {
+        let mut game = Game::default();
+        game.set_outcome_score(Outcome::Win, 10);
+        game.set_shape_score(Shape::PAPER, 4_000_000_000);
+        assert_eq!(
+            Round::new(Shape::ROCK, Shape::PAPER).score(&game),
+            4_000_000_010
+        );
+        let guide = [(Shape::ROCK, Column::Y); 5];
+        assert_eq!(
+            total_score(&game, &guide, Interpretation::Shape),
+            Ok(5 * 4_000_000_010)
+        );
+    }
+
+    #[test]
+    fn locates_bad_columns() {
+        let error = parse("A Y\nB Q\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a second column (X, Y or Z), found `Q`\n  |\n2 | B Q\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn rejects_rounds_without_separator() {
+        let error = parse("A Y\nAY\n".as_bytes()).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 2, column 1: expected a round such as `A Y`, found `AY`"));
+    }
+
+    #[test]
+    fn classifies_bad_rounds() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let error = |line| parse_round(&game, &Interpretation::ALL, 7, line).unwrap_err();
+        assert!(matches!(error("F Y"), StrategyGuideError::BadShape { .. }));
+        assert!(matches!(error("A Q"), StrategyGuideError::BadColumn { .. }));
+        assert!(matches!(
+            error("A V"),
+            StrategyGuideError::BadOutcome { .. }
+        ));
+        assert!(matches!(
+            error("AY"),
+            StrategyGuideError::MissingSeparator { .. }
+        ));
+        let extra = error("A Y Z");
+        assert!(matches!(extra, StrategyGuideError::ExtraColumns { .. }));
+        assert_eq!((extra.round(), extra.line()), (7, "A Y Z"));
+        assert_eq!(extra.error().column(), Some(5));
+        let shapes = [Interpretation::Shape];
+        assert!(parse_round(&game, &shapes, 7, "A V").is_ok());
+    }
+
+    #[test]
+    fn keeps_going_past_bad_rounds() {
+        let game = Game::default();
+        let input = "A Y\nB Q\nC Z\nAY\n";
+        let error = parse_keep_going(&game, &Interpretation::ALL, input.as_bytes()).unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("line 2, column 3: expected a second column"));
+        assert!(message.contains("\nline 4, column 1: expected a round such as `A Y`"));
+        assert!(message.ends_with("\n2 rounds can't be read"));
+        let input = "A Y\nC Z\n";
+        assert_eq!(
+            parse_keep_going(&game, &Interpretation::ALL, input.as_bytes()).unwrap(),
+            vec![(Shape::ROCK, Column::Y), (Shape::SCISSORS, Column::Z)]
+        );
+    }
+
+    #[test]
+    fn keeps_going_past_columns_that_are_not_outcomes() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let input = "A V\nB W\n";
+        let error = parse_keep_going(&game, &Interpretation::ALL, input.as_bytes()).unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("line 1, column 3: expected an outcome (X, Y or Z), found `V`"));
+        assert!(message.contains("\nline 2, column 3: expected an outcome (X, Y or Z), found `W`"));
+        assert!(message.ends_with("\n2 rounds can't be read"));
+    }
+
+    #[test]
+    fn parses_columns() {
+        let game = Game::default();
+        assert_eq!(game.opponent("A"), Ok(Shape::ROCK));
+        assert_eq!(game.column("Z"), Ok(Column::Z));
+        assert!(game.opponent("X").is_err());
+        assert!(game.column("A").is_err());
+    }
+}
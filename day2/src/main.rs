@@ -1,154 +1,216 @@
-use std::io;
-use std::result;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-#[derive(Clone, Copy)]
-enum Shape {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+use aoc_common::input::Source;
+use aoc_common::{error, Args, Error, Format, ParseError, Result, Solution};
+use day2::analysis::Analysis;
+use day2::tournament::{Strategy, StrategyName, Tournament};
+use day2::trace::Trace;
+use day2::{Column, Game, Interpretation, Outcome, Shape, Variant};
+
+/// A `--score NAME=N` option, setting what playing a shape scores.
+struct ShapeScore {
+    name: String,
+    score: u32,
 }
 
-#[derive(Debug, Clone)]
-struct ParseShapeError {
-    invalid: String,
+impl FromStr for ShapeScore {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (name, score) = s
+            .split_once('=')
+            .ok_or_else(|| ParseError::new("a shape's score such as `Rock=1`", s))?;
+        let score = error::parse(score, "a score").map_err(|e| e.within(s, score))?;
+        Ok(Self {
+            name: name.to_string(),
+            score,
+        })
+    }
 }
 
-impl std::fmt::Display for ParseShapeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "ParseShapeError {}", self.invalid)
-    }
+/// What to print about the strategy guide.
+enum Mode {
+    Answers(Format),
+    Total(Interpretation),
+    Compare,
+    Analysis(Format),
+    /// Every round, read as one interpretation or each in turn.
+    Trace(Option<Interpretation>),
+    Tournament {
+        strategies: Vec<StrategyName>,
+        rounds: u64,
+        seed: u64,
+    },
 }
 
-impl std::error::Error for ParseShapeError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+/// Prints the total from reading the guide each way, side by side.
+fn print_comparison(game: &Game, guide: &[(Shape, Column)]) -> Result<()> {
+    let mut totals = Vec::new();
+    for interpretation in Interpretation::ALL {
+        let total = day2::total_score(game, guide, interpretation)?;
+        totals.push((interpretation.to_string(), total.to_string()));
     }
+    let widths = totals
+        .iter()
+        .map(|(interpretation, total)| interpretation.len().max(total.len()))
+        .collect::<Vec<_>>();
+    let row = |cells: Vec<&String>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+    println!("{}", row(totals.iter().map(|(name, _)| name).collect()));
+    println!("{}", row(totals.iter().map(|(_, total)| total).collect()));
+    Ok(())
 }
 
-impl FromStr for Shape {
-    type Err = ParseShapeError;
-
-    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
-        match s {
-            "A" => Ok(Shape::Rock),
-            "B" => Ok(Shape::Paper),
-            "C" => Ok(Shape::Scissors),
-            invalid => Err(ParseShapeError {
-                invalid: invalid.to_owned(),
-            }),
+fn try_main() -> Result<()> {
+    let mut args = Args::from_env();
+    let usage = format!(
+        "usage: {} [--game FILE] [--win N] [--draw N] [--loss N] [--score SHAPE=N]... [--keep-going] \
+         [--read-as shape|outcome | --trace [--read-as shape|outcome] | --compare | --analyse [--output text|json] \
+         | --tournament [--strategy guide|frequency|beat-last|random]... [--rounds N] [--seed N] \
+         | --output text|json] [PATH]",
+        args.program()
+    );
+    let game = args.value::<PathBuf>("game")?;
+    let mut outcome_scores = Vec::new();
+    for outcome in Outcome::ALL {
+        if let Some(score) = args.value::<u32>(&outcome.to_string())? {
+            outcome_scores.push((outcome, score));
         }
     }
-}
-
-enum Outcome {
-    Win = 6,
-    Loss = 0,
-    Draw = 3,
-}
-
-#[derive(Debug, Clone)]
-struct ParseOutcomeError {
-    invalid: String,
-}
-
-impl std::fmt::Display for ParseOutcomeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "ParseOutcomeError {}", self.invalid)
+    let shape_scores = args.values::<ShapeScore>("score")?;
+    let keep_going = args.flag("keep-going");
+    let interpretation = args.value::<Interpretation>("read-as")?;
+    let trace = args.flag("trace");
+    let compare = args.flag("compare");
+    let analyse = args.flag("analyse");
+    let tournament = args.flag("tournament");
+    let strategies = args.values::<StrategyName>("strategy")?;
+    let rounds = args.value::<u64>("rounds")?;
+    let seed = args.value::<u64>("seed")?;
+    let format = args.value::<Format>("output")?;
+    let path = args.positional::<PathBuf>("an input path")?;
+    args.finish().map_err(|_| Error::Usage(usage.clone()))?;
+
+    let tournament_options = !strategies.is_empty() || rounds.is_some() || seed.is_some();
+    let mode = match (interpretation, compare, analyse, tournament, format) {
+        _ if tournament_options && !tournament => return Err(Error::Usage(usage)),
+        (interpretation, false, false, false, None) if trace => Mode::Trace(interpretation),
+        _ if trace => return Err(Error::Usage(usage)),
+        (None, false, false, false, format) => Mode::Answers(format.unwrap_or_default()),
+        (Some(interpretation), false, false, false, None) => Mode::Total(interpretation),
+        (None, true, false, false, None) => Mode::Compare,
+        (None, false, true, false, format) => Mode::Analysis(format.unwrap_or_default()),
+        (None, false, false, true, None) => Mode::Tournament {
+            strategies: match strategies.len() {
+                0 => vec![
+                    StrategyName::Guide,
+                    StrategyName::Frequency,
+                    StrategyName::BeatLast,
+                    StrategyName::Random,
+                ],
+                1 => return Err(Error::Usage(usage)),
+                _ => strategies,
+            },
+            rounds: rounds.unwrap_or(1000),
+            seed: seed.unwrap_or(0),
+        },
+        _ => return Err(Error::Usage(usage)),
+    };
+    let mut game = match game {
+        Some(path) => Game::parse(Source::File(path).open()?)?,
+        None => Game::default(),
+    };
+    for (outcome, score) in outcome_scores {
+        game.set_outcome_score(outcome, score);
     }
-}
-
-impl std::error::Error for ParseOutcomeError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+    for ShapeScore { name, score } in shape_scores {
+        let shape = game
+            .shape(&name)
+            .ok_or_else(|| Error::Usage(format!("no shape is called `{}`", name)))?;
+        game.set_shape_score(shape, score);
     }
-}
-
-impl FromStr for Outcome {
-    type Err = ParseOutcomeError;
-
-    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
-        match s {
-            "X" => Ok(Outcome::Loss),
-            "Y" => Ok(Outcome::Draw),
-            "Z" => Ok(Outcome::Win),
-            invalid => Err(ParseOutcomeError {
-                invalid: invalid.to_string(),
-            }),
+    let source = Source::resolve(path.as_deref(), day2::Day2.day());
+    // The guide is checked for only the readings the mode makes of it.
+    let guide = |interpretations: &[Interpretation]| {
+        if keep_going {
+            day2::parse_keep_going(&game, interpretations, source.open()?)
+        } else {
+            day2::parse_as(&game, interpretations, source.open()?)
         }
-    }
-}
-
-struct Round {
-    opponent: Shape,
-    you: Shape,
-}
-
-impl Round {
-    fn new(opponent: Shape, you: Shape) -> Self {
-        Round { opponent, you }
-    }
-
-    fn outcome(&self) -> Outcome {
-        match (&self.opponent, &self.you) {
-            (Shape::Rock, Shape::Rock) => Outcome::Draw,
-            (Shape::Rock, Shape::Paper) => Outcome::Win,
-            (Shape::Rock, Shape::Scissors) => Outcome::Loss,
-            (Shape::Paper, Shape::Rock) => Outcome::Loss,
-            (Shape::Paper, Shape::Paper) => Outcome::Draw,
-            (Shape::Paper, Shape::Scissors) => Outcome::Win,
-            (Shape::Scissors, Shape::Rock) => Outcome::Win,
-            (Shape::Scissors, Shape::Paper) => Outcome::Loss,
-            (Shape::Scissors, Shape::Scissors) => Outcome::Draw,
+    };
+    match mode {
+        Mode::Answers(format) => {
+            return aoc_common::run_with(
+                &Variant {
+                    game: game.clone(),
+                    keep_going,
+                },
+                &source,
+                format,
+            )
         }
-    }
-
-    ///
-    ///  The score for a single round is the score for the shape you selected
-    /// (1 for Rock, 2 for Paper, and 3 for Scissors)
-    /// plus the score for the outcome of the round
-    /// (0 if you lost, 3 if the round was a draw, and 6 if you won).
-    //
-    fn score(&self) -> u8 {
-        self.you as u8 + self.outcome() as u8
-    }
-}
-
-fn solve(opponent: Shape, outcome: Outcome) -> Shape {
-    match outcome {
-        Outcome::Draw => opponent,
-        Outcome::Loss => match opponent {
-            Shape::Rock => Shape::Scissors,
-            Shape::Paper => Shape::Rock,
-            Shape::Scissors => Shape::Paper,
-        },
-        Outcome::Win => match opponent {
-            Shape::Rock => Shape::Paper,
-            Shape::Paper => Shape::Scissors,
-            Shape::Scissors => Shape::Rock,
-        },
-    }
-}
-
-fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
-    let stdin = io::stdin();
-    let mut total_score: u32 = 0;
-    loop {
-        let mut buf = String::new();
-        let n = stdin.read_line(&mut buf)?;
-        if n == 0 {
-            break;
+        Mode::Total(interpretation) => {
+            println!(
+                "{}",
+                day2::total_score(&game, &guide(&[interpretation])?, interpretation)?
+            )
         }
-        if let Some((opponent_str, outcome_str)) = buf.trim_end().split_once(' ') {
-            let opponent = opponent_str.parse::<Shape>()?;
-            let outcome = outcome_str.parse::<Outcome>()?;
-            let you = solve(opponent, outcome);
-            let round = Round::new(opponent, you);
-            total_score += round.score() as u32;
-        } else {
-            panic!("Couldn't parse {}", buf);
+        Mode::Compare => print_comparison(&game, &guide(&Interpretation::ALL)?)?,
+        Mode::Analysis(format) => {
+            let analysis = Analysis::new(&game, &guide(&[Interpretation::Shape])?)?;
+            match format {
+                Format::Text => print!("{}", analysis),
+                Format::Json => println!("{}", analysis.to_json()),
+            }
+        }
+        Mode::Trace(interpretation) => {
+            let interpretations = match interpretation {
+                Some(interpretation) => vec![interpretation],
+                None => Interpretation::ALL.to_vec(),
+            };
+            let guide = guide(&interpretations)?;
+            for (i, interpretation) in interpretations.into_iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                print!("{}", Trace::new(&game, &guide, interpretation)?);
+            }
+        }
+        Mode::Tournament {
+            strategies,
+            rounds,
+            seed,
+        } => {
+            // The guide is only read if a strategy follows it.
+            let mut shapes = None;
+            let mut players = Vec::new();
+            for name in strategies {
+                players.push(match name {
+                    StrategyName::Guide => {
+                        if shapes.is_none() {
+                            let guide = guide(&[Interpretation::Shape])?;
+                            shapes = Some(guide.iter().map(|(_, column)| column.shape()).collect());
+                        }
+                        Strategy::Guide(shapes.clone().unwrap_or_default())
+                    }
+                    StrategyName::Frequency => Strategy::Frequency,
+                    StrategyName::BeatLast => Strategy::BeatLast,
+                    StrategyName::Random => Strategy::Random,
+                });
+            }
+            print!("{}", Tournament::play(&game, &players, rounds, seed)?);
         }
     }
-    println!("{}", total_score);
     Ok(())
 }
+
+fn main() {
+    aoc_common::main(try_main)
+}
@@ -0,0 +1,503 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{Error, ParseError};
+
+use crate::{solve, table, Game, Outcome, Round, Shape};
+
+/// How a player picks each shape in a tournament.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Plays these shapes in turn, starting over when they run out.
+    Guide(Vec<Shape>),
+    /// Beats whichever shape the opponent has played most often.
+    Frequency,
+    /// Beats whichever shape the opponent played last.
+    BeatLast,
+    /// Plays any shape at random.
+    Random,
+}
+
+impl Strategy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Guide(_) => "guide",
+            Strategy::Frequency => "frequency",
+            Strategy::BeatLast => "beat-last",
+            Strategy::Random => "random",
+        }
+    }
+}
+
+/// A strategy named on the command line, before any guide has been read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrategyName {
+    Guide,
+    Frequency,
+    BeatLast,
+    Random,
+}
+
+impl FromStr for StrategyName {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "guide" => Ok(StrategyName::Guide),
+            "frequency" => Ok(StrategyName::Frequency),
+            "beat-last" => Ok(StrategyName::BeatLast),
+            "random" => Ok(StrategyName::Random),
+            invalid => Err(ParseError::new(
+                "a strategy (guide, frequency, beat-last or random)",
+                invalid,
+            )),
+        }
+    }
+}
+
+/// A strategy's score that no longer fits in a `u64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TournamentError {
+    /// The strategy's score against `opponent` overflowed in `round`,
+    /// counting from 1.
+    MatchupOverflow {
+        strategy: String,
+        opponent: String,
+        round: u64,
+    },
+    /// The strategy's score over the whole tournament overflowed.
+    Overflow { strategy: String },
+}
+
+impl fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MatchupOverflow {
+                strategy,
+                opponent,
+                round,
+            } => write!(
+                f,
+                "round {} of {} vs {}: the score of {} overflows",
+                round, strategy, opponent, strategy
+            ),
+            Self::Overflow { strategy } => {
+                write!(f, "the tournament score of {} overflows", strategy)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TournamentError {}
+
+impl From<TournamentError> for Error {
+    fn from(e: TournamentError) -> Self {
+        Error::puzzle(e)
+    }
+}
+
+/// A small, fast generator of pseudo-random numbers (SplitMix64), so that a
+/// tournament played with the same seed always plays out the same way.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 up to but not including `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// A strategy being played, with what it has seen of its opponent so far.
+struct Player<'a> {
+    strategy: &'a Strategy,
+    rng: Rng,
+    /// How many times the opponent has played each shape.
+    counts: Vec<u64>,
+    last: Option<Shape>,
+    /// How many rounds have been played.
+    turn: u64,
+}
+
+impl<'a> Player<'a> {
+    fn new(game: &Game, strategy: &'a Strategy, rng: Rng) -> Self {
+        Self {
+            strategy,
+            rng,
+            counts: vec![0; game.shapes().count()],
+            last: None,
+            turn: 0,
+        }
+    }
+
+    fn play(&mut self, game: &Game) -> Shape {
+        let first = Shape(0);
+        let beat = |shape| solve(game, shape, Outcome::Win).unwrap_or(first);
+        match self.strategy {
+            Strategy::Guide(shapes) if shapes.is_empty() => first,
+            Strategy::Guide(shapes) => shapes[(self.turn % shapes.len() as u64) as usize],
+            Strategy::Frequency => {
+                // The earliest shape in the game wins a tie.
+                let counts = &self.counts;
+                let most = (0..counts.len()).rev().max_by_key(|&i| counts[i]);
+                match most {
+                    Some(i) if counts[i] > 0 => beat(Shape(i)),
+                    _ => first,
+                }
+            }
+            Strategy::BeatLast => self.last.map_or(first, beat),
+            Strategy::Random => Shape(self.rng.below(game.shapes().count())),
+        }
+    }
+
+    /// Remembers that the opponent played `shape` this round.
+    fn saw(&mut self, shape: Shape) {
+        self.counts[shape.0] += 1;
+        self.last = Some(shape);
+        self.turn += 1;
+    }
+}
+
+/// How one strategy did against another, from the first's side.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matchup {
+    /// The places of the two strategies in the tournament's list.
+    pub players: (usize, usize),
+    pub wins: u64,
+    pub draws: u64,
+    pub losses: u64,
+    pub scores: (u64, u64),
+}
+
+impl Matchup {
+    /// The share of the rounds the first strategy won.
+    pub fn win_rate(&self) -> f64 {
+        rate(self.wins, self.wins + self.draws + self.losses)
+    }
+}
+
+/// A strategy's record over the whole tournament.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub player: usize,
+    pub wins: u64,
+    pub draws: u64,
+    pub losses: u64,
+    pub score: u64,
+}
+
+impl Standing {
+    pub fn rounds(&self) -> u64 {
+        self.wins + self.draws + self.losses
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        rate(self.wins, self.rounds())
+    }
+}
+
+fn rate(wins: u64, rounds: u64) -> f64 {
+    if rounds == 0 {
+        0.0
+    } else {
+        wins as f64 / rounds as f64
+    }
+}
+
+/// Every strategy playing every other for the same number of rounds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tournament {
+    pub names: Vec<String>,
+    pub rounds: u64,
+    pub seed: u64,
+    pub matchups: Vec<Matchup>,
+    /// Best first: by score, then by wins.
+    pub standings: Vec<Standing>,
+}
+
+impl Tournament {
+    pub fn play(
+        game: &Game,
+        strategies: &[Strategy],
+        rounds: u64,
+        seed: u64,
+    ) -> Result<Self, TournamentError> {
+        let names = names(strategies);
+        let mut standings = (0..strategies.len())
+            .map(|player| Standing {
+                player,
+                ..Standing::default()
+            })
+            .collect::<Vec<_>>();
+        let mut matchups = Vec::new();
+        for a in 0..strategies.len() {
+            for b in a + 1..strategies.len() {
+                // Each matchup's seed depends only on the seed and the two
+                // players, so adding a strategy doesn't change other results.
+                let key = Rng::new(a as u64).next_u64() ^ Rng::new(!(b as u64)).next_u64();
+                let mut seeds = Rng::new(seed ^ key);
+                let mut players = [&strategies[a], &strategies[b]]
+                    .map(|strategy| Player::new(game, strategy, Rng::new(seeds.next_u64())));
+                let matchup = play_matchup(game, &mut players, (a, b), &names, rounds)?;
+                for (player, won, lost, score) in [
+                    (a, matchup.wins, matchup.losses, matchup.scores.0),
+                    (b, matchup.losses, matchup.wins, matchup.scores.1),
+                ] {
+                    let standing = &mut standings[player];
+                    standing.wins += won;
+                    standing.losses += lost;
+                    standing.draws += matchup.draws;
+                    standing.score = standing.score.checked_add(score).ok_or_else(|| {
+                        TournamentError::Overflow {
+                            strategy: names[player].clone(),
+                        }
+                    })?;
+                }
+                matchups.push(matchup);
+            }
+        }
+        standings.sort_by_key(|s| std::cmp::Reverse((s.score, s.wins)));
+        Ok(Self {
+            names,
+            rounds,
+            seed,
+            matchups,
+            standings,
+        })
+    }
+}
+
+fn play_matchup(
+    game: &Game,
+    players: &mut [Player; 2],
+    indices: (usize, usize),
+    names: &[String],
+    rounds: u64,
+) -> Result<Matchup, TournamentError> {
+    let mut matchup = Matchup {
+        players: indices,
+        wins: 0,
+        draws: 0,
+        losses: 0,
+        scores: (0, 0),
+    };
+    for round in 1..=rounds {
+        let a = players[0].play(game);
+        let b = players[1].play(game);
+        players[0].saw(b);
+        players[1].saw(a);
+        let (round_a, round_b) = (Round::new(b, a), Round::new(a, b));
+        match round_a.outcome(game) {
+            Outcome::Win => matchup.wins += 1,
+            Outcome::Draw => matchup.draws += 1,
+            Outcome::Loss => matchup.losses += 1,
+        }
+        let overflow = |strategy: usize, opponent: usize| TournamentError::MatchupOverflow {
+            strategy: names[strategy].clone(),
+            opponent: names[opponent].clone(),
+            round,
+        };
+        let (a_index, b_index) = indices;
+        matchup.scores.0 = matchup
+            .scores
+            .0
+            .checked_add(round_a.score(game))
+            .ok_or_else(|| overflow(a_index, b_index))?;
+        matchup.scores.1 = matchup
+            .scores
+            .1
+            .checked_add(round_b.score(game))
+            .ok_or_else(|| overflow(b_index, a_index))?;
+    }
+    Ok(matchup)
+}
+
+/// The strategies' names, numbered where one is entered more than once.
+fn names(strategies: &[Strategy]) -> Vec<String> {
+    strategies
+        .iter()
+        .enumerate()
+        .map(|(i, strategy)| {
+            let name = strategy.name();
+            let same = strategies.iter().filter(|s| s.name() == name).count();
+            if same == 1 {
+                name.to_string()
+            } else {
+                let nth = strategies[..=i].iter().filter(|s| s.name() == name).count();
+                format!("{} {}", name, nth)
+            }
+        })
+        .collect()
+}
+
+impl fmt::Display for Tournament {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} rounds per matchup, seed {}", self.rounds, self.seed)?;
+        writeln!(f)?;
+        let league = self
+            .standings
+            .iter()
+            .map(|standing| {
+                [
+                    self.names[standing.player].clone(),
+                    standing.rounds().to_string(),
+                    standing.wins.to_string(),
+                    standing.draws.to_string(),
+                    standing.losses.to_string(),
+                    standing.score.to_string(),
+                    format!("{:.3}", standing.win_rate()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        table(
+            f,
+            [
+                "strategy", "rounds", "wins", "draws", "losses", "score", "win rate",
+            ],
+            league,
+        )?;
+        writeln!(f)?;
+        let matchups = self
+            .matchups
+            .iter()
+            .map(|matchup| {
+                let (a, b) = matchup.players;
+                [
+                    format!("{} vs {}", self.names[a], self.names[b]),
+                    matchup.wins.to_string(),
+                    matchup.draws.to_string(),
+                    matchup.losses.to_string(),
+                    format!("{:.3}", matchup.win_rate()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        table(
+            f,
+            ["matchup", "wins", "draws", "losses", "win rate"],
+            matchups,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strategies() -> Vec<Strategy> {
+        vec![
+            Strategy::Guide(vec![Shape::ROCK, Shape::ROCK, Shape::PAPER]),
+            Strategy::Frequency,
+            Strategy::BeatLast,
+            Strategy::Random,
+        ]
+    }
+
+    #[test]
+    fn beat_last_beats_a_fixed_shape() {
+        let game = Game::default();
+        let strategies = [Strategy::Guide(vec![Shape::ROCK]), Strategy::BeatLast];
+        let tournament = Tournament::play(&game, &strategies, 10, 0).unwrap();
+        let matchup = &tournament.matchups[0];
+        // Both open with Rock; after that Paper covers Rock every round.
+        assert_eq!((matchup.wins, matchup.draws, matchup.losses), (0, 1, 9));
+        assert_eq!(matchup.scores, (1 + 3 + 9, 1 + 3 + 9 * 8));
+        assert_eq!(tournament.standings[0].player, 1);
+    }
+
+    #[test]
+    fn frequency_beats_the_most_common_shape() {
+        let game = Game::default();
+        let strategy = Strategy::Frequency;
+        let mut player = Player::new(&game, &strategy, Rng::new(0));
+        assert_eq!(player.play(&game), Shape::ROCK);
+        for shape in [Shape::SCISSORS, Shape::PAPER, Shape::SCISSORS] {
+            player.saw(shape);
+        }
+        assert_eq!(player.play(&game), Shape::ROCK);
+    }
+
+    #[test]
+    fn same_seed_same_tournament() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let first = Tournament::play(&game, &strategies(), 500, 7).unwrap();
+        let again = Tournament::play(&game, &strategies(), 500, 7).unwrap();
+        let other = Tournament::play(&game, &strategies(), 500, 8).unwrap();
+        assert_eq!(first, again);
+        assert_ne!(first.matchups, other.matchups);
+    }
+
+    #[test]
+    fn adding_a_strategy_keeps_other_matchups() {
+        let game = Game::default();
+        let mut strategies = vec![Strategy::Random, Strategy::Frequency, Strategy::Random];
+        let before = Tournament::play(&game, &strategies, 200, 11).unwrap();
+        strategies.push(Strategy::BeatLast);
+        let after = Tournament::play(&game, &strategies, 200, 11).unwrap();
+        for matchup in &before.matchups {
+            assert!(after.matchups.contains(matchup));
+        }
+    }
+
+    #[test]
+    fn overflows_name_the_strategy() {
+        let error = TournamentError::MatchupOverflow {
+            strategy: "guide".to_string(),
+            opponent: "random 2".to_string(),
+            round: 7,
+        };
+        assert_eq!(
+            error.to_string(),
+            "round 7 of guide vs random 2: the score of guide overflows"
+        );
+        let error = TournamentError::Overflow {
+            strategy: "frequency".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "the tournament score of frequency overflows"
+        );
+    }
+
+    #[test]
+    fn every_pair_plays_every_round() {
+        let tournament = Tournament::play(&Game::default(), &strategies(), 100, 1).unwrap();
+        assert_eq!(tournament.matchups.len(), 6);
+        for standing in &tournament.standings {
+            assert_eq!(standing.rounds(), 300);
+        }
+        let wins = tournament.standings.iter().map(|s| s.wins).sum::<u64>();
+        let losses = tournament.standings.iter().map(|s| s.losses).sum::<u64>();
+        assert_eq!(wins, losses);
+    }
+
+    #[test]
+    fn random_is_roughly_uniform() {
+        let mut rng = Rng::new(42);
+        let mut counts = [0; 3];
+        for _ in 0..30_000 {
+            counts[rng.below(3)] += 1;
+        }
+        assert!(counts.iter().all(|&n| (9_000..11_000).contains(&n)));
+    }
+
+    #[test]
+    fn numbers_repeated_strategies() {
+        let strategies = [Strategy::Random, Strategy::Frequency, Strategy::Random];
+        assert_eq!(
+            names(&strategies),
+            vec!["random 1", "frequency", "random 2"]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use crate::{
+    table, Column, Game, Interpretation, Outcome, Round, ScoreError, Shape, StrategyGuideError,
+};
+
+/// How one round of the guide was scored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub round: Round,
+    pub outcome: Outcome,
+    pub shape_points: u32,
+    pub outcome_points: u32,
+    /// The total score after this round.
+    pub total: u64,
+}
+
+/// Every round of the guide as scored with the second column read one way.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub interpretation: Interpretation,
+    pub steps: Vec<Step>,
+    /// The names of the game's shapes.
+    pub names: Vec<String>,
+}
+
+impl Trace {
+    pub fn new(
+        game: &Game,
+        guide: &[(Shape, Column)],
+        interpretation: Interpretation,
+    ) -> Result<Self, ScoreError> {
+        let mut steps = Vec::new();
+        let mut total = 0u64;
+        for (i, &(opponent, column)) in guide.iter().enumerate() {
+            let round = interpretation
+                .round(game, opponent, column)
+                .ok_or_else(|| {
+                    ScoreError::Round(Box::new(StrategyGuideError::not_an_outcome(
+                        game,
+                        i + 1,
+                        opponent,
+                        column,
+                    )))
+                })?;
+            total = total
+                .checked_add(round.score(game))
+                .ok_or(ScoreError::Overflow { round: i + 1 })?;
+            let outcome = round.outcome(game);
+            steps.push(Step {
+                round,
+                outcome,
+                shape_points: game.rule(round.you).score,
+                outcome_points: game.outcome_score(outcome),
+                total,
+            });
+        }
+        Ok(Self {
+            interpretation,
+            steps,
+            names: game
+                .shapes()
+                .map(|shape| game.rule(shape).name.clone())
+                .collect(),
+        })
+    }
+
+    pub fn total(&self) -> u64 {
+        self.steps.last().map_or(0, |step| step.total)
+    }
+
+    /// How many rounds ended in `outcome`.
+    pub fn count(&self, outcome: Outcome) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.outcome == outcome)
+            .count()
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                [
+                    (i + 1).to_string(),
+                    self.names[step.round.opponent.0].clone(),
+                    self.names[step.round.you.0].clone(),
+                    step.outcome.to_string(),
+                    step.shape_points.to_string(),
+                    step.outcome_points.to_string(),
+                    step.total.to_string(),
+                ]
+            })
+            .collect();
+        table(
+            f,
+            [
+                "round",
+                "opponent",
+                "you",
+                "outcome",
+                "shape points",
+                "outcome points",
+                "total",
+            ],
+            rows,
+        )?;
+        writeln!(
+            f,
+            "{} won, {} drawn, {} lost, total {} with the column read as {}",
+            self.count(Outcome::Win),
+            self.count(Outcome::Draw),
+            self.count(Outcome::Loss),
+            self.total(),
+            self.interpretation
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<(Shape, Column)> {
+        crate::parse(include_str!("../tests/example.txt").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn traces_example() {
+        let game = Game::default();
+        let trace = Trace::new(&game, &example(), Interpretation::Outcome).unwrap();
+        let totals = trace
+            .steps
+            .iter()
+            .map(|step| step.total)
+            .collect::<Vec<_>>();
+        assert_eq!(totals, vec![4, 5, 12]);
+        assert_eq!(
+            trace.steps[2],
+            Step {
+                round: Round::new(Shape::SCISSORS, Shape::ROCK),
+                outcome: Outcome::Win,
+                shape_points: 1,
+                outcome_points: 6,
+                total: 12,
+            }
+        );
+        assert_eq!(trace.total(), 12);
+        assert_eq!(trace.count(Outcome::Draw), 1);
+    }
+
+    #[test]
+    fn prints_a_line_per_round() {
+        let trace = Trace::new(&Game::default(), &example(), Interpretation::Shape).unwrap();
+        assert_eq!(
+            trace.to_string(),
+            "\
+round  opponent       you  outcome  shape points  outcome points  total
+1          Rock     Paper      win             2               6      8
+2         Paper      Rock     loss             1               0      9
+3      Scissors  Scissors     draw             3               3     15
+1 won, 1 drawn, 1 lost, total 15 with the column read as shape
+"
+        );
+    }
+}
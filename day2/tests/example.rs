@@ -0,0 +1,35 @@
+use aoc_common::{Answers, Solution};
+use day2::{Column, Shape};
+
+const EXAMPLE: &str = include_str!("example.txt");
+
+#[test]
+fn parses_example() {
+    let guide = day2::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(
+        guide,
+        vec![
+            (Shape::ROCK, Column::Y),
+            (Shape::PAPER, Column::X),
+            (Shape::SCISSORS, Column::Z),
+        ]
+    );
+}
+
+#[test]
+fn part1_example() {
+    let guide = day2::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day2::part1(&guide), Ok(15));
+}
+
+#[test]
+fn part2_example() {
+    let guide = day2::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day2::part2(&guide), Ok(12));
+}
+
+#[test]
+fn solves_example() {
+    let answers = day2::Day2.solve(&mut EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(answers, Answers::new(15, 12));
+}
@@ -0,0 +1,99 @@
+//! Compares finding shared items with `Rucksack` bitsets against hash sets,
+//! on a large generated input.
+//!
+//! Run with `cargo bench -p day3`, optionally passing the number of rucksacks.
+
+use std::collections::{HashMap, HashSet};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use day3::priority;
+
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Rucksacks of 24 to 48 items, each with one item type in both compartments.
+fn generate(count: usize) -> Vec<String> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = |n: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % n as u64) as usize
+    };
+    (0..count)
+        .map(|_| {
+            let half = 12 + next(13);
+            let first = (0..half).map(|_| LOWER[next(26)]).collect::<Vec<_>>();
+            let mut second = (0..half).map(|_| UPPER[next(26)]).collect::<Vec<_>>();
+            second[next(half)] = first[next(half)];
+            String::from_utf8(first.into_iter().chain(second).collect()).unwrap()
+        })
+        .collect()
+}
+
+fn with_hash_sets(rucksacks: &[String]) -> u32 {
+    let mut priorities = 0;
+    for rucksack in rucksacks {
+        let (first, second) = rucksack.split_at(rucksack.len() / 2);
+        let first = first.chars().collect::<HashSet<_>>();
+        if let Some(item) = second.chars().find(|item| first.contains(item)) {
+            priorities += priority(&item).unwrap_or(0);
+        }
+    }
+    for group in rucksacks.chunks(3) {
+        let mut frequency = HashMap::new();
+        for rucksack in group {
+            for item in rucksack.chars().collect::<HashSet<_>>() {
+                let freq = frequency.entry(item).or_insert(0);
+                *freq += 1;
+                if *freq == 3 {
+                    priorities += priority(&item).unwrap_or(0);
+                }
+            }
+        }
+    }
+    priorities
+}
+
+fn with_bitsets(rucksacks: &[(usize, String)]) -> u32 {
+    day3::part1(rucksacks).unwrap() + day3::part2(rucksacks)
+}
+
+/// The fastest of a few runs of `f`.
+fn time(f: impl Fn() -> u32) -> (u32, Duration) {
+    let mut best = Duration::MAX;
+    let mut answer = 0;
+    for _ in 0..5 {
+        let start = Instant::now();
+        answer = black_box(f());
+        best = best.min(start.elapsed());
+    }
+    (answer, best)
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`; any number is the input size.
+    let count = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(300_000);
+    let rucksacks = generate(count);
+    let (expected, hashed) = time(|| with_hash_sets(black_box(&rucksacks)));
+    // Numbered by line, as `day3::parse` reads them.
+    let numbered = rucksacks
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(i, r)| (i + 1, r))
+        .collect::<Vec<_>>();
+    let (answer, bitset) = time(|| with_bitsets(black_box(&numbered)));
+    assert_eq!(answer, expected, "the two ways disagree");
+    println!("{} rucksacks", count);
+    println!("hash sets  {:>10.2?}", hashed);
+    println!("bitsets    {:>10.2?}", bitset);
+    println!(
+        "speedup    {:>9.1}x",
+        hashed.as_secs_f64() / bitset.as_secs_f64()
+    );
+}
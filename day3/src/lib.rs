@@ -0,0 +1,364 @@
+use std::fmt;
+use std::io::BufRead;
+use std::result;
+
+use aoc_common::input::{self, Group};
+use aoc_common::{Answers, Error, ParseError, Registry, Result, Solution};
+
+/// The priority of an item type: 1 through 26 for `a` through `z`, then 27
+/// through 52 for `A` through `Z`.
+pub fn priority(item: &char) -> Option<u32> {
+    if *item >= 'A' && *item <= 'Z' {
+        return Some(*item as u32 - 38);
+    }
+    if *item >= 'a' && *item <= 'z' {
+        return Some(*item as u32 - 96);
+    }
+    None
+}
+
+/// The item type with the given priority.
+pub fn item(priority: u32) -> Option<char> {
+    match priority {
+        1..=26 => char::from_u32(priority + 96),
+        27..=52 => char::from_u32(priority + 38),
+        _ => None,
+    }
+}
+
+/// A set of item types, one bit of a `u64` for each priority.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rucksack(u64);
+
+impl Rucksack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `item`, returning false if it has no priority.
+    pub fn insert(&mut self, item: char) -> bool {
+        match priority(&item) {
+            Some(priority) => {
+                self.0 |= 1 << priority;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        priority(&item).is_some_and(|priority| self.0 & 1 << priority != 0)
+    }
+
+    /// The item types in both rucksacks.
+    pub fn intersection(self, other: Rucksack) -> Rucksack {
+        Rucksack(self.0 & other.0)
+    }
+
+    /// The item types in either rucksack.
+    pub fn union(self, other: Rucksack) -> Rucksack {
+        Rucksack(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The item types in order of priority.
+    pub fn items(self) -> impl Iterator<Item = char> {
+        let mut mask = self.0;
+        std::iter::from_fn(move || {
+            if mask == 0 {
+                return None;
+            }
+            let priority = mask.trailing_zeros();
+            mask &= mask - 1;
+            item(priority)
+        })
+    }
+
+    /// The sum of the priorities of the item types.
+    pub fn priorities(self) -> u32 {
+        self.items().filter_map(|item| priority(&item)).sum()
+    }
+}
+
+/// Items without a priority, which `parse` never gives, are left out.
+impl FromIterator<char> for Rucksack {
+    fn from_iter<I: IntoIterator<Item = char>>(items: I) -> Self {
+        let mut rucksack = Rucksack::new();
+        for item in items {
+            rucksack.insert(item);
+        }
+        rucksack
+    }
+}
+
+const GROUP_SIZE: u8 = 3;
+
+/// A rucksack whose compartments can't be checked, with the number of its
+/// line, counting from 1, and its items as read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompartmentError {
+    /// The items can't be split evenly between the two compartments.
+    OddLength { line: usize, items: String },
+    /// No item type is in both compartments.
+    NoSharedItem { line: usize, items: String },
+}
+
+impl CompartmentError {
+    /// The error pointing at the rucksack's line.
+    pub fn error(&self) -> ParseError {
+        let (expected, line, items) = match self {
+            Self::OddLength { line, items } => (
+                "an even number of items, to split between two compartments",
+                line,
+                items,
+            ),
+            Self::NoSharedItem { line, items } => ("an item in both compartments", line, items),
+        };
+        ParseError::new(expected, items.as_str()).at(*line, items, items)
+    }
+}
+
+impl fmt::Display for CompartmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error())
+    }
+}
+
+impl std::error::Error for CompartmentError {}
+
+impl From<CompartmentError> for Error {
+    fn from(e: CompartmentError) -> Self {
+        Error::puzzle(e)
+    }
+}
+
+/// Checks that `line` holds only items.
+fn parse_rucksack(number: usize, line: &str) -> result::Result<(), ParseError> {
+    match line
+        .char_indices()
+        .find(|(_, item)| priority(item).is_none())
+    {
+        Some((j, item)) => {
+            let found = &line[j..j + item.len_utf8()];
+            let error = ParseError::new("an item (a letter)", found);
+            Err(error.at(number, line, found))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Reads the rucksacks, one line of items each, with their line numbers
+/// counting from 1. Blank lines are skipped.
+pub fn parse(input: impl BufRead) -> Result<Group> {
+    let mut rucksacks = Vec::new();
+    for (i, line) in input::lines(input).enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        parse_rucksack(i + 1, &line)?;
+        rucksacks.push((i + 1, line));
+    }
+    Ok(rucksacks)
+}
+
+/// The item found in both compartments of the rucksack on line `line`, the
+/// first half of its items being the first compartment.
+pub fn shared_item(line: usize, items: &str) -> result::Result<char, CompartmentError> {
+    if !items.len().is_multiple_of(2) {
+        return Err(CompartmentError::OddLength {
+            line,
+            items: items.to_string(),
+        });
+    }
+    let (first, second) = items.split_at(items.len() / 2);
+    let first = first.chars().collect::<Rucksack>();
+    let second = second.chars().collect::<Rucksack>();
+    first
+        .intersection(second)
+        .items()
+        .next()
+        .ok_or_else(|| CompartmentError::NoSharedItem {
+            line,
+            items: items.to_string(),
+        })
+}
+
+/// Sums the priorities of the item found in both compartments of each
+/// rucksack. Rucksacks are expected as read by `parse`, which only accepts
+/// items that have a priority.
+pub fn part1(rucksacks: &[(usize, String)]) -> result::Result<u32, CompartmentError> {
+    let mut priorities = 0;
+    for (line, rucksack) in rucksacks {
+        priorities += priority(&shared_item(*line, rucksack)?).unwrap_or(0);
+    }
+    Ok(priorities)
+}
+
+/// The priority of the badge carried by every elf in `group`, or 0 if the
+/// group is short of elves or has no badge.
+fn badge_priority(group: &[(usize, String)]) -> u32 {
+    if group.len() < GROUP_SIZE as usize {
+        return 0;
+    }
+    group
+        .iter()
+        .map(|(_, rucksack)| rucksack.chars().collect::<Rucksack>())
+        .reduce(Rucksack::intersection)
+        .map_or(0, Rucksack::priorities)
+}
+
+/// Sums the priorities of the badge carried by every elf in each group.
+/// Rucksacks are expected as read by `parse`.
+pub fn part2(rucksacks: &[(usize, String)]) -> u32 {
+    rucksacks
+        .chunks(GROUP_SIZE as usize)
+        .map(badge_priority)
+        .sum()
+}
+
+/// Answers both parts in a single pass over the input, keeping only the
+/// current group of rucksacks.
+fn answers(input: &mut dyn BufRead) -> Result<Answers> {
+    let (mut compartments, mut badges) = (0, 0);
+    let mut group = Vec::with_capacity(GROUP_SIZE as usize);
+    for (i, line) in input::lines(input).enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        parse_rucksack(i + 1, &line)?;
+        compartments += priority(&shared_item(i + 1, &line)?).unwrap_or(0);
+        group.push((i + 1, line));
+        if group.len() == GROUP_SIZE as usize {
+            badges += badge_priority(&group);
+            group.clear();
+        }
+    }
+    badges += badge_priority(&group);
+    Ok(Answers::new(compartments, badges))
+}
+
+/// Day 3's puzzle.
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        answers(input)
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Box::new(Day3));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_of_items() {
+        assert_eq!(priority(&'a'), Some(1));
+        assert_eq!(priority(&'z'), Some(26));
+        assert_eq!(priority(&'A'), Some(27));
+        assert_eq!(priority(&'Z'), Some(52));
+    }
+
+    #[test]
+    fn parse_rejects_non_items() {
+        let error = parse("vJrwpWtwJgWrhcsFMMfFFhFp\nabc1de\n".as_bytes()).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 2, column 4: expected an item (a letter), found `1`"));
+    }
+
+    #[test]
+    fn finds_the_item_in_both_compartments() {
+        assert_eq!(shared_item(1, "vJrwpWtwJgWrhcsFMMfFFhFp"), Ok('p'));
+        assert_eq!(
+            shared_item(2, "abcab"),
+            Err(CompartmentError::OddLength {
+                line: 2,
+                items: "abcab".to_string(),
+            })
+        );
+        assert_eq!(
+            shared_item(3, "abcdef"),
+            Err(CompartmentError::NoSharedItem {
+                line: 3,
+                items: "abcdef".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn solve_reports_rucksack_errors() {
+        let error = Day3.solve(&mut "abca\nabcd\nab\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected an item in both compartments, found `abcd`\n  |\n2 | abcd\n  | ^^^^"
+        );
+        let error = Day3.solve(&mut "abca\n\nabc\n".as_bytes()).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "line 3, column 1: expected an even number of items, to split between two compartments"
+        ));
+        let error = Day3.solve(&mut "abca\nab1\n".as_bytes()).unwrap_err();
+        assert!(error.to_string().starts_with("line 2, column 3"));
+    }
+
+    #[test]
+    fn rucksacks_are_sets_of_items() {
+        let first = "abcA".chars().collect::<Rucksack>();
+        let second = "cAZ".chars().collect::<Rucksack>();
+        assert_eq!(first.intersection(second).items().collect::<String>(), "cA");
+        assert_eq!(first.union(second).items().collect::<String>(), "abcAZ");
+        assert!(first.contains('A') && !first.contains('Z') && !first.contains('1'));
+        assert_eq!((first.len(), first.priorities()), (4, 1 + 2 + 3 + 27));
+        let mut rucksack = Rucksack::new();
+        assert!(rucksack.is_empty());
+        assert!(rucksack.insert('z') && !rucksack.insert('-'));
+        assert_eq!(rucksack.items().collect::<Vec<_>>(), vec!['z']);
+    }
+
+    #[test]
+    fn item_of_priority() {
+        for priority in 1..=52 {
+            assert_eq!(
+                item(priority).and_then(|item| super::priority(&item)),
+                Some(priority)
+            );
+        }
+        assert_eq!((item(0), item(53)), (None, None));
+    }
+
+    #[test]
+    fn skips_blank_lines() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\n\n";
+        assert_eq!(parse(input.as_bytes()).unwrap().len(), 1);
+        let rucksacks = parse("abca\n\nabcd\n".as_bytes()).unwrap();
+        let error = part1(&rucksacks).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected an item in both compartments, found `abcd`\n  |\n3 | abcd\n  | ^^^^"
+        );
+        let answers = Day3.solve(&mut input.as_bytes()).unwrap();
+        assert_eq!(answers, Answers::new(16, 0));
+    }
+
+    #[test]
+    fn priority_of_non_items() {
+        assert_eq!(priority(&'1'), None);
+        assert_eq!(priority(&' '), None);
+    }
+}
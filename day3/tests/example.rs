@@ -0,0 +1,21 @@
+use aoc_common::{Answers, Solution};
+
+const EXAMPLE: &str = include_str!("example.txt");
+
+#[test]
+fn part1_example() {
+    let rucksacks = day3::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day3::part1(&rucksacks), Ok(157));
+}
+
+#[test]
+fn part2_example() {
+    let rucksacks = day3::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day3::part2(&rucksacks), 70);
+}
+
+#[test]
+fn solves_example() {
+    let answers = day3::Day3.solve(&mut EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(answers, Answers::new(157, 70));
+}
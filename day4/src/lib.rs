@@ -0,0 +1,158 @@
+use std::io::BufRead;
+use std::result;
+use std::str;
+
+use aoc_common::error;
+use aoc_common::input;
+use aoc_common::{Answers, ParseError, Registry, Result, Solution};
+
+/// The inclusive range of sections an elf is assigned to clean.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Assignment {
+    start: u32,
+    end: u32,
+}
+
+impl Assignment {
+    pub fn new(start: u32, end: u32) -> Self {
+        Assignment { start, end }
+    }
+
+    /// Whether `other` lies entirely within this assignment.
+    pub fn contains(&self, other: &Assignment) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether this assignment and `other` share any section.
+    pub fn overlaps(&self, other: &Assignment) -> bool {
+        self.contains(other)
+            || (self.start <= other.start && other.start <= self.end)
+            || (self.start <= other.end && other.end <= self.end)
+            || other.contains(self)
+    }
+}
+
+impl str::FromStr for Assignment {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        if let Some((start_str, end_str)) = s.split_once('-') {
+            let start = error::parse::<u32>(start_str, "a section number")
+                .map_err(|e| e.within(s, start_str))?;
+            let end = error::parse::<u32>(end_str, "a section number")
+                .map_err(|e| e.within(s, end_str))?;
+            Ok(Self::new(start, end))
+        } else {
+            Err(ParseError::new("a `-`-delimited section range", s))
+        }
+    }
+}
+
+/// Reads the pairs of assignments, one comma-separated pair per line.
+pub fn parse(input: impl BufRead) -> Result<Vec<(Assignment, Assignment)>> {
+    let mut pairs = Vec::new();
+    for (i, line) in input::lines(input).enumerate() {
+        let line = line?;
+        let (first_str, second_str) = line.split_once(',').ok_or_else(|| {
+            ParseError::new("a comma-separated pair of assignments", &line).at(i + 1, &line, &line)
+        })?;
+        let first = first_str
+            .parse::<Assignment>()
+            .map_err(|e| e.at(i + 1, &line, first_str))?;
+        let second = second_str
+            .parse::<Assignment>()
+            .map_err(|e| e.at(i + 1, &line, second_str))?;
+        pairs.push((first, second));
+    }
+    Ok(pairs)
+}
+
+/// The number of pairs where one assignment contains the other.
+pub fn part1(pairs: &[(Assignment, Assignment)]) -> usize {
+    pairs
+        .iter()
+        .filter(|(first, second)| first.contains(second) || second.contains(first))
+        .count()
+}
+
+/// The number of pairs whose assignments overlap.
+pub fn part2(pairs: &[(Assignment, Assignment)]) -> usize {
+    pairs
+        .iter()
+        .filter(|(first, second)| first.overlaps(second))
+        .count()
+}
+
+/// Day 4's puzzle.
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        let pairs = parse(input)?;
+        Ok(Answers::new(part1(&pairs), part2(&pairs)))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Box::new(Day4));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_only_when_within() {
+        assert!(Assignment::new(2, 8).contains(&Assignment::new(3, 7)));
+        assert!(Assignment::new(4, 6).contains(&Assignment::new(6, 6)));
+        assert!(!Assignment::new(3, 7).contains(&Assignment::new(2, 8)));
+        assert!(!Assignment::new(2, 4).contains(&Assignment::new(4, 5)));
+    }
+
+    #[test]
+    fn overlaps_is_symmetric() {
+        let pairs = [
+            (Assignment::new(5, 7), Assignment::new(7, 9), true),
+            (Assignment::new(2, 8), Assignment::new(3, 7), true),
+            (Assignment::new(2, 6), Assignment::new(4, 8), true),
+            (Assignment::new(2, 4), Assignment::new(6, 8), false),
+            (Assignment::new(2, 3), Assignment::new(4, 5), false),
+        ];
+        for (first, second, overlaps) in pairs {
+            assert_eq!(first.overlaps(&second), overlaps);
+            assert_eq!(second.overlaps(&first), overlaps);
+        }
+    }
+
+    #[test]
+    fn locates_bad_section_numbers() {
+        let error = parse("2-4,6-8\n2-4,16-x\n".as_bytes()).unwrap_err();
+        match error {
+            aoc_common::Error::Parse(error) => {
+                assert_eq!(error.line(), Some(2));
+                assert_eq!(error.column(), Some(8));
+                assert_eq!(error.found(), "x");
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn rejects_missing_comma() {
+        let error = parse("2-4 6-8\n".as_bytes()).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 1, column 1: expected a comma"));
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!("2-4".parse::<Assignment>(), Ok(Assignment::new(2, 4)));
+        assert!("2".parse::<Assignment>().is_err());
+        assert!("2-x".parse::<Assignment>().is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use aoc_common::{Answers, Solution};
+use day4::Assignment;
+
+const EXAMPLE: &str = include_str!("example.txt");
+
+#[test]
+fn parses_example() {
+    let pairs = day4::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(pairs.len(), 6);
+    assert_eq!(pairs[0], (Assignment::new(2, 4), Assignment::new(6, 8)));
+}
+
+#[test]
+fn part1_example() {
+    let pairs = day4::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day4::part1(&pairs), 2);
+}
+
+#[test]
+fn part2_example() {
+    let pairs = day4::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day4::part2(&pairs), 4);
+}
+
+#[test]
+fn solves_example() {
+    let answers = day4::Day4.solve(&mut EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(answers, Answers::new(2, 4));
+}
@@ -0,0 +1,451 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io::BufRead;
+use std::result::Result;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use aoc_common::error;
+use aoc_common::input::{self, Group};
+use aoc_common::{Answers, ParseError, Registry, Solution};
+use regex::Regex;
+
+/// A crate, labelled with a single letter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crate(pub char);
+
+impl Display for Crate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self(label) = self;
+        write!(f, "[{}]", label)
+    }
+}
+
+impl FromStr for Crate {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let re = Regex::new(r"^\[([A-Z])\]$").unwrap();
+        if let Some(captures) = re.captures(s) {
+            let label = captures.get(1).unwrap().as_str().chars().next().unwrap();
+            Ok(Crate(label))
+        } else {
+            Err(ParseError::new("a crate such as `[A]`", s))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    NotEnoughCrates,
+    InvalidSourceStack,
+    InvalidDestStack,
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEnoughCrates => write!(f, "not enough crates"),
+            Self::InvalidSourceStack => write!(f, "invalid source stack"),
+            Self::InvalidDestStack => write!(f, "invalid dest stack"),
+        }
+    }
+}
+
+impl Error for MoveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl From<MoveError> for aoc_common::Error {
+    fn from(error: MoveError) -> Self {
+        aoc_common::Error::puzzle(error)
+    }
+}
+
+/// The model of crane doing the rearranging.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crane {
+    /// Moves crates one at a time, reversing their order.
+    CrateMover9000,
+    /// Moves several crates at once, keeping their order.
+    CrateMover9001,
+}
+
+/// A step of the rearrangement procedure. Stacks are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub amount: u32,
+    pub source: usize,
+    pub dest: usize,
+}
+
+impl Move {
+    pub fn execute(&self, stacks: &mut [Vec<Crate>], crane: Crane) -> Result<(), MoveError> {
+        if self.source == 0 || self.source > stacks.len() {
+            return Err(MoveError::InvalidSourceStack);
+        }
+
+        if self.dest == 0 || self.dest > stacks.len() {
+            return Err(MoveError::InvalidDestStack);
+        }
+
+        let source = &mut stacks[self.source - 1];
+        let at = source
+            .len()
+            .checked_sub(self.amount as usize)
+            .ok_or(MoveError::NotEnoughCrates)?;
+
+        let mut crates = source.split_off(at);
+        if let Crane::CrateMover9000 = crane {
+            crates.reverse();
+        }
+        stacks[self.dest - 1].append(&mut crates);
+        Ok(())
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.amount, self.source, self.dest
+        )
+    }
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let re = Regex::new(r"^move ([0-9]+) from ([0-9]+) to ([0-9]+)$").unwrap();
+        if let Some(captures) = re.captures(s) {
+            // The regex ensures that every group matched and holds only digits,
+            // but the numbers can still be too large.
+            let amount_str = captures.get(1).unwrap().as_str();
+            let amount = error::parse::<u32>(amount_str, "a number of crates")
+                .map_err(|e| e.within(s, amount_str))?;
+            let source_str = captures.get(2).unwrap().as_str();
+            let source = error::parse::<usize>(source_str, "a stack number")
+                .map_err(|e| e.within(s, source_str))?;
+            let dest_str = captures.get(3).unwrap().as_str();
+            let dest = error::parse::<usize>(dest_str, "a stack number")
+                .map_err(|e| e.within(s, dest_str))?;
+            Ok(Move {
+                amount,
+                source,
+                dest,
+            })
+        } else {
+            Err(ParseError::new("a move such as `move 1 from 2 to 3`", s))
+        }
+    }
+}
+
+/// A stack, numbered from 1, that has no crate on top.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmptyStackError(pub usize);
+
+impl Display for EmptyStackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self(stack) = self;
+        write!(f, "stack {} is empty", stack)
+    }
+}
+
+impl Error for EmptyStackError {}
+
+impl From<EmptyStackError> for aoc_common::Error {
+    fn from(error: EmptyStackError) -> Self {
+        aoc_common::Error::puzzle(error)
+    }
+}
+
+/// The labels of the crates on top of each stack.
+pub fn top_labels(stacks: &[Vec<Crate>]) -> Result<String, EmptyStackError> {
+    stacks
+        .iter()
+        .enumerate()
+        .map(|(i, s)| match s.last() {
+            Some(Crate(label)) => Ok(*label),
+            None => Err(EmptyStackError(i + 1)),
+        })
+        .collect()
+}
+
+/// Reads the drawing of the stacks, the group of lines ending with the stack
+/// numbers, returning the stacks bottom first.
+fn parse_stacks(mut stack_lines: Group) -> aoc_common::Result<Vec<Vec<Crate>>> {
+    // Now we build up the stacks
+    stack_lines.reverse();
+    let mut stack_lines_iter = stack_lines.iter();
+    let (number, stack_numbers) = stack_lines_iter
+        .next()
+        .expect("`Groups` never yields an empty group");
+    for label in stack_numbers.split_whitespace() {
+        error::parse::<usize>(label, "a stack number under the drawing")
+            .map_err(|e| e.at(*number, stack_numbers, label))?;
+    }
+    let num_stacks = stack_numbers.split_whitespace().count();
+    let mut stacks = Vec::new();
+    for _ in 0..num_stacks {
+        let stack = Vec::new();
+        stacks.push(stack);
+    }
+
+    for (number, line) in stack_lines_iter {
+        // Each crate takes up four characters: its label in brackets and the
+        // space separating it from the next stack.
+        let mut rest = line.trim_end();
+        let mut i = 0;
+        while !rest.is_empty() {
+            let end = rest.char_indices().nth(4).map_or(rest.len(), |(j, _)| j);
+            let (chunk, tail) = rest.split_at(end);
+            let crate_str = chunk.trim_end();
+            if !crate_str.is_empty() {
+                let crate_ = crate_str
+                    .parse::<Crate>()
+                    .map_err(|e| e.at(*number, line, crate_str))?;
+                let stack = stacks.get_mut(i).ok_or_else(|| {
+                    let expected = format!("a crate on one of the {} numbered stacks", num_stacks);
+                    ParseError::new(expected, crate_str).at(*number, line, crate_str)
+                })?;
+                stack.push(crate_);
+            }
+            i += 1;
+            rest = tail;
+        }
+    }
+    Ok(stacks)
+}
+
+fn parse_moves(move_lines: Group) -> aoc_common::Result<Vec<Move>> {
+    // Now we parse the moves
+    let mut moves = Vec::new();
+    for (number, line) in move_lines {
+        let mv = line
+            .parse::<Move>()
+            .map_err(|e| e.at(number, &line, &line))?;
+        moves.push(mv);
+    }
+    Ok(moves)
+}
+
+/// The starting stacks of crates, bottom first, and the moves to make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Procedure {
+    pub stacks: Vec<Vec<Crate>>,
+    pub moves: Vec<Move>,
+}
+
+impl Procedure {
+    /// Runs every move on a copy of the stacks with the given crane and returns
+    /// the rearranged stacks.
+    pub fn rearrange(&self, crane: Crane) -> Result<Vec<Vec<Crate>>, MoveError> {
+        let mut stacks = self.stacks.clone();
+        for mv in self.moves.iter() {
+            mv.execute(&mut stacks, crane)?;
+        }
+        Ok(stacks)
+    }
+}
+
+/// Reads the drawing of the starting stacks and, after a blank line, the
+/// rearrangement procedure.
+pub fn parse(input: impl BufRead) -> aoc_common::Result<Procedure> {
+    let mut groups = input::groups(input);
+    // Only an input of blank lines has no groups, so there is no line to
+    // point at.
+    let drawing = groups
+        .next()
+        .transpose()?
+        .ok_or_else(|| ParseError::new("a drawing of the stacks", ""))?;
+    let stacks = parse_stacks(drawing)?;
+    let mut moves = Vec::new();
+    for move_lines in groups {
+        moves.extend(parse_moves(move_lines?)?);
+    }
+    Ok(Procedure { stacks, moves })
+}
+
+/// The top crates once the CrateMover 9000 has run the procedure.
+pub fn part1(procedure: &Procedure) -> aoc_common::Result<String> {
+    Ok(top_labels(&procedure.rearrange(Crane::CrateMover9000)?)?)
+}
+
+/// The top crates once the CrateMover 9001 has run the procedure.
+pub fn part2(procedure: &Procedure) -> aoc_common::Result<String> {
+    Ok(top_labels(&procedure.rearrange(Crane::CrateMover9001)?)?)
+}
+
+/// Day 5's puzzle.
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> aoc_common::Result<Answers> {
+        let procedure = parse(input)?;
+        Ok(Answers::new(part1(&procedure)?, part2(&procedure)?))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Box::new(Day5));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stacks() -> Vec<Vec<Crate>> {
+        vec![
+            vec![Crate('Z'), Crate('N')],
+            vec![Crate('M'), Crate('C'), Crate('D')],
+            vec![Crate('P')],
+        ]
+    }
+
+    #[test]
+    fn parses_crates() {
+        assert_eq!("[A]".parse::<Crate>(), Ok(Crate('A')));
+        assert!("[a]".parse::<Crate>().is_err());
+        assert!("A".parse::<Crate>().is_err());
+    }
+
+    #[test]
+    fn parses_moves() {
+        assert_eq!(
+            "move 3 from 1 to 2".parse::<Move>(),
+            Ok(Move {
+                amount: 3,
+                source: 1,
+                dest: 2,
+            })
+        );
+        assert!("move 3 from 1".parse::<Move>().is_err());
+    }
+
+    #[test]
+    fn locates_bad_crates() {
+        let input = "    [D]\n[N] [c]\n 1   2\n\nmove 1 from 2 to 1\n";
+        match parse(input.as_bytes()) {
+            Err(aoc_common::Error::Parse(error)) => {
+                assert_eq!(error.line(), Some(2));
+                assert_eq!(error.column(), Some(5));
+                assert_eq!(error.found(), "[c]");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn locates_bad_moves() {
+        let input = "[A]\n 1\n\nmove 1 from 1 to 1\nmove one from 1 to 1\n";
+        match parse(input.as_bytes()) {
+            Err(aoc_common::Error::Parse(error)) => {
+                assert_eq!(error.line(), Some(5));
+                assert_eq!(error.column(), Some(1));
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn crate_mover_9000_reverses_crates() {
+        let mut stacks = stacks();
+        let mv = "move 2 from 2 to 3".parse::<Move>().unwrap();
+        mv.execute(&mut stacks, Crane::CrateMover9000).unwrap();
+        assert_eq!(stacks[1], vec![Crate('M')]);
+        assert_eq!(stacks[2], vec![Crate('P'), Crate('D'), Crate('C')]);
+    }
+
+    #[test]
+    fn crate_mover_9001_keeps_order() {
+        let mut stacks = stacks();
+        let mv = "move 2 from 2 to 3".parse::<Move>().unwrap();
+        mv.execute(&mut stacks, Crane::CrateMover9001).unwrap();
+        assert_eq!(stacks[1], vec![Crate('M')]);
+        assert_eq!(stacks[2], vec![Crate('P'), Crate('C'), Crate('D')]);
+    }
+
+    #[test]
+    fn execute_rejects_unknown_stacks() {
+        let mut stacks = stacks();
+        let mv = "move 1 from 4 to 1".parse::<Move>().unwrap();
+        assert_eq!(
+            mv.execute(&mut stacks, Crane::CrateMover9000),
+            Err(MoveError::InvalidSourceStack)
+        );
+        let mv = "move 1 from 1 to 4".parse::<Move>().unwrap();
+        assert_eq!(
+            mv.execute(&mut stacks, Crane::CrateMover9000),
+            Err(MoveError::InvalidDestStack)
+        );
+    }
+
+    #[test]
+    fn execute_rejects_stack_zero() {
+        let mut stacks = stacks();
+        let mv = "move 1 from 0 to 1".parse::<Move>().unwrap();
+        assert_eq!(
+            mv.execute(&mut stacks, Crane::CrateMover9000),
+            Err(MoveError::InvalidSourceStack)
+        );
+    }
+
+    #[test]
+    fn execute_rejects_taking_too_many_crates() {
+        let mut stacks = stacks();
+        let mv = "move 4 from 2 to 1".parse::<Move>().unwrap();
+        assert_eq!(
+            mv.execute(&mut stacks, Crane::CrateMover9001),
+            Err(MoveError::NotEnoughCrates)
+        );
+        assert_eq!(stacks, self::stacks());
+    }
+
+    #[test]
+    fn parse_rejects_huge_numbers() {
+        assert!("move 99999999999 from 1 to 2".parse::<Move>().is_err());
+    }
+
+    #[test]
+    fn parse_rejects_crates_beyond_the_numbered_stacks() {
+        let input = "[A] [B]\n 1\n\n";
+        assert!(parse(input.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_missing_drawing() {
+        let error = parse("\nmove 1 from 1 to 2\n".as_bytes()).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 2, column 1: expected a stack number"));
+    }
+
+    #[test]
+    fn parse_rejects_empty_input() {
+        let error = parse("\n \n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a drawing of the stacks, found nothing"
+        );
+    }
+
+    #[test]
+    fn top_labels_of_stacks() {
+        assert_eq!(top_labels(&stacks()), Ok("NDP".to_string()));
+    }
+
+    #[test]
+    fn top_labels_of_empty_stack() {
+        let stacks = vec![vec![Crate('A')], vec![]];
+        assert_eq!(top_labels(&stacks), Err(EmptyStackError(2)));
+    }
+}
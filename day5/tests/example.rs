@@ -0,0 +1,44 @@
+use aoc_common::{Answers, Solution};
+use day5::{Crate, Move};
+
+const EXAMPLE: &str = include_str!("example.txt");
+
+#[test]
+fn parses_example() {
+    let procedure = day5::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(
+        procedure.stacks,
+        vec![
+            vec![Crate('Z'), Crate('N')],
+            vec![Crate('M'), Crate('C'), Crate('D')],
+            vec![Crate('P')],
+        ]
+    );
+    assert_eq!(procedure.moves.len(), 4);
+    assert_eq!(
+        procedure.moves[0],
+        Move {
+            amount: 1,
+            source: 2,
+            dest: 1,
+        }
+    );
+}
+
+#[test]
+fn part1_example() {
+    let procedure = day5::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day5::part1(&procedure).unwrap(), "CMZ");
+}
+
+#[test]
+fn part2_example() {
+    let procedure = day5::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day5::part2(&procedure).unwrap(), "MCD");
+}
+
+#[test]
+fn solves_example() {
+    let answers = day5::Day5.solve(&mut EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(answers, Answers::new("CMZ", "MCD"));
+}
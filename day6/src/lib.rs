@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use aoc_common::input;
+use aoc_common::{Answers, Error, Registry, Result, Solution};
+
+/// Size of the start-of-packet marker.
+const PACKET_MARKER_SIZE: usize = 4;
+
+/// Size of the start-of-message marker.
+const MESSAGE_MARKER_SIZE: usize = 14;
+
+/// Day 6's puzzle.
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        let datastream = parse(input)?;
+        let packet = part1(&datastream).ok_or_else(|| no_marker("start-of-packet"))?;
+        let message = part2(&datastream).ok_or_else(|| no_marker("start-of-message"))?;
+        Ok(Answers::new(packet, message))
+    }
+}
+
+fn no_marker(kind: &str) -> Error {
+    Error::Puzzle(format!("the datastream has no {} marker", kind).into())
+}
+
+/// Reads the datastream, the first line of the input.
+pub fn parse(input: impl BufRead) -> Result<String> {
+    Ok(input::lines(input).next().transpose()?.unwrap_or_default())
+}
+
+/// Where the first start-of-packet marker ends.
+pub fn part1(datastream: &str) -> Option<usize> {
+    find_marker(datastream, PACKET_MARKER_SIZE)
+}
+
+/// Where the first start-of-message marker ends.
+pub fn part2(datastream: &str) -> Option<usize> {
+    find_marker(datastream, MESSAGE_MARKER_SIZE)
+}
+
+/// Returns how many characters of `datastream` have to be read before the
+/// last `marker_size` of them are all different.
+pub fn find_marker(datastream: &str, marker_size: usize) -> Option<usize> {
+    if marker_size == 0 {
+        return Some(0);
+    }
+    let chars = datastream.chars().collect::<Vec<_>>();
+    let mut seen = HashMap::new();
+
+    // Use a hashmap histogram windowed to the length of the marker and if its length
+    // matches the length of the marker then we know the characters are unique. Relying
+    // on HashMap::len() to be O(1) yields an overall runtime of O(n)
+    for (i, &ch) in chars.iter().enumerate() {
+        if i >= marker_size {
+            // Decrement an occurence of the character left adjecent to the left side of the window
+            let c = chars[i - marker_size];
+            if let Some(val) = seen.get_mut(&c) {
+                *val -= 1;
+                // ...and if occurences is 0, then remove the entry from the hashmap, so that its length
+                // will correspond to the number of unique characters seen in the window
+                if *val == 0 {
+                    seen.remove(&c);
+                }
+            }
+        }
+        let entry = seen.entry(ch).or_insert(0);
+        *entry += 1;
+        if seen.len() == marker_size {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Box::new(Day6));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_at_start() {
+        assert_eq!(find_marker("abcd", 4), Some(4));
+    }
+
+    #[test]
+    fn marker_after_repeats() {
+        assert_eq!(find_marker("aabbcdef", 4), Some(7));
+    }
+
+    #[test]
+    fn marker_counts_characters_not_bytes() {
+        assert_eq!(find_marker("ééabcd", 4), Some(5));
+    }
+
+    #[test]
+    fn empty_marker() {
+        assert_eq!(find_marker("aaaa", 0), Some(0));
+    }
+
+    #[test]
+    fn solve_reports_missing_marker() {
+        assert!(Day6.solve(&mut "abcabc\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn no_marker() {
+        assert_eq!(find_marker("abcabcabc", 4), None);
+        assert_eq!(find_marker("", 4), None);
+    }
+}
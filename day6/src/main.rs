@@ -1,44 +1,38 @@
-use std::collections::HashMap;
-use std::env;
-use std::error;
-use std::io;
-use std::process;
-use std::result;
+use std::path::PathBuf;
 
-fn main() -> result::Result<(), Box<dyn error::Error>> {
-    let mut args = env::args();
-    if args.len() != 2 {
-        eprintln!("usage: {} <marker_size>", args.nth(0).unwrap());
-        process::exit(1);
-    }
-    let marker_size = args.nth(1).unwrap().parse::<usize>()?;
-    let stdin = io::stdin();
-    let mut datastream = String::new();
-    stdin.read_line(&mut datastream)?;
-    let datastream = datastream.trim_end();
-    let mut seen = HashMap::new();
+use aoc_common::input::Source;
+use aoc_common::{Args, Error, Format, Result, Solution};
+
+fn try_main() -> Result<()> {
+    let mut args = Args::from_env();
+    let usage = format!(
+        "usage: {} [--marker-size N | --output text|json] [PATH]",
+        args.program()
+    );
+    let marker_size = args.value::<usize>("marker-size")?;
+    let format = args.value::<Format>("output")?;
+    let path = args.positional::<PathBuf>("an input path")?;
+    args.finish().map_err(|_| Error::Usage(usage.clone()))?;
 
-    // Use a hashmap histogram windowed to the length of the marker and if its length
-    // matches the length of the marker then we know the characters are unique. Relying
-    // on HashMap::len() to be O(1) yields an overall runtime of O(n)
-    for (i, ch) in datastream.char_indices() {
-        if i >= marker_size {
-            // Decrement an occurence of the character left adjecent to the left side of the window
-            let c = datastream.chars().nth(i - marker_size).unwrap();
-            let val = seen.get_mut(&c).unwrap();
-            *val -= 1;
-            // ...and if occurences is 0, then remove the entry from the hashmap, so that its length
-            // will correspond to the number of unique characters seen in the window
-            if *val == 0 {
-                seen.remove(&c);
+    let source = Source::resolve(path.as_deref(), day6::Day6.day());
+    match (marker_size, format) {
+        (Some(_), Some(_)) => Err(Error::Usage(usage)),
+        (Some(marker_size), None) => {
+            let datastream = day6::parse(source.open()?)?;
+            match day6::find_marker(&datastream, marker_size) {
+                Some(marker) => {
+                    println!("{}", marker);
+                    Ok(())
+                }
+                None => Err(Error::Puzzle(
+                    format!("the datastream has no marker of size {}", marker_size).into(),
+                )),
             }
         }
-        let entry = seen.entry(ch).or_insert(0);
-        *entry += 1;
-        if seen.len() == marker_size {
-            println!("{}", i + 1);
-            break;
-        }
+        (None, format) => aoc_common::run_with(&day6::Day6, &source, format.unwrap_or_default()),
     }
-    Ok(())
+}
+
+fn main() {
+    aoc_common::main(try_main)
 }
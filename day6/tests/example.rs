@@ -0,0 +1,33 @@
+use aoc_common::{Answers, Solution};
+
+/// The example datastreams with where their packet and message markers end.
+const EXAMPLES: [(&str, usize, usize); 5] = [
+    ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+    ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+    ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+    ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+    ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+];
+
+#[test]
+fn part1_examples() {
+    for (datastream, packet, _) in EXAMPLES {
+        assert_eq!(day6::part1(datastream), Some(packet), "{}", datastream);
+    }
+}
+
+#[test]
+fn part2_examples() {
+    for (datastream, _, message) in EXAMPLES {
+        assert_eq!(day6::part2(datastream), Some(message), "{}", datastream);
+    }
+}
+
+#[test]
+fn solves_examples() {
+    for (datastream, packet, message) in EXAMPLES {
+        let input = format!("{}\n", datastream);
+        let answers = day6::Day6.solve(&mut input.as_bytes()).unwrap();
+        assert_eq!(answers, Answers::new(packet, message));
+    }
+}
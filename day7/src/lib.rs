@@ -0,0 +1,474 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io::BufRead;
+
+use aoc_common::input;
+use aoc_common::{Answers, Error, ParseError, Registry, Result, Solution};
+
+/// A directory whose total size doesn't fit in a `u64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverflowError {
+    pub directory: String,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the size of directory `{}` overflows", self.directory)
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+impl From<OverflowError> for Error {
+    fn from(e: OverflowError) -> Self {
+        Error::puzzle(e)
+    }
+}
+
+/// A directory of the device's filesystem, holding files and other
+/// directories by name.
+#[derive(Debug)]
+pub struct Directory {
+    name: String,
+    files: HashMap<String, u64>,
+    directories: HashMap<String, Directory>,
+}
+
+impl Directory {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            files: HashMap::new(),
+            directories: HashMap::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Iterates over the names and sizes of the files directly in this directory.
+    pub fn files(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.files.iter().map(|(name, size)| (name.as_str(), *size))
+    }
+
+    /// Iterates over the directories directly in this directory.
+    pub fn directories(&self) -> impl Iterator<Item = &Directory> {
+        self.directories.values()
+    }
+
+    /// The total size of the files in this directory and all of its
+    /// subdirectories.
+    pub fn size(&self) -> std::result::Result<u64, OverflowError> {
+        let overflow = || OverflowError {
+            directory: self.name.clone(),
+        };
+        let mut size = 0u64;
+        for file in self.files.values() {
+            size = size.checked_add(*file).ok_or_else(overflow)?;
+        }
+        for directory in self.directories.values() {
+            size = size.checked_add(directory.size()?).ok_or_else(overflow)?;
+        }
+        Ok(size)
+    }
+
+    pub fn add_file(&mut self, name: impl Into<String>, size: u64) {
+        self.files.insert(name.into(), size);
+    }
+
+    pub fn add_directory(&mut self, directory: Directory) {
+        let name = directory.name.clone();
+        self.directories.insert(name, directory);
+    }
+
+    pub fn get_mut_directory(&mut self, name: impl Into<String>) -> Option<&mut Directory> {
+        let name = name.into();
+        self.directories.get_mut(&name)
+    }
+}
+
+/// A line of terminal output.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    ChangeDirectory(String),
+    ChangeToParentDirectory,
+    ListDirectory,
+    Directory(String),
+    File(String, u64),
+    UnexpectedCharacters(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChangeDirectory(name) => write!(f, "$ cd {}", name),
+            Self::ChangeToParentDirectory => write!(f, "$ cd .."),
+            Self::ListDirectory => write!(f, "$ ls"),
+            Self::Directory(name) => write!(f, "dir {}", name),
+            Self::File(name, size) => write!(f, "{} {}", size, name),
+            Self::UnexpectedCharacters(line) => write!(f, "{}", line),
+        }
+    }
+}
+
+/// Splits terminal output into tokens, one per non-empty line.
+pub struct Lexer {
+    lines: VecDeque<(usize, String)>,
+    /// The number of lines in the whole input.
+    line_count: usize,
+    /// The number and text of the line the last token came from.
+    line: Option<(usize, String)>,
+}
+
+impl Lexer {
+    fn next_non_empty_line(&mut self) -> Option<String> {
+        let mut front_line = self.lines.pop_front();
+
+        // Skip empty lines
+        loop {
+            match front_line {
+                Some((_, line)) if line.is_empty() => front_line = self.lines.pop_front(),
+                Some((number, line)) => {
+                    self.line = Some((number, line.clone()));
+                    return Some(line);
+                }
+                None => {
+                    self.line = None;
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// The number and text of the line the last token was read from, or
+    /// `None` once the input is exhausted.
+    pub fn line(&self) -> Option<(usize, &str)> {
+        self.line
+            .as_ref()
+            .map(|(number, line)| (*number, line.as_str()))
+    }
+}
+
+impl Iterator for Lexer {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(line) = self.next_non_empty_line() {
+            if line == "$ ls" {
+                Some(Token::ListDirectory)
+            } else if line == "$ cd .." {
+                Some(Token::ChangeToParentDirectory)
+            } else if line.starts_with("$ cd ") {
+                let name = line.strip_prefix("$ cd ")?;
+                Some(Token::ChangeDirectory(name.to_string()))
+            } else if line.starts_with("dir ") {
+                let name = line.strip_prefix("dir ")?;
+                Some(Token::Directory(name.to_string()))
+            } else if let Some((size, name)) = line
+                .split_once(' ')
+                .and_then(|(size, name)| Some((size.parse::<u64>().ok()?, name)))
+            {
+                Some(Token::File(name.to_string(), size))
+            } else {
+                Some(Token::UnexpectedCharacters(line))
+            }
+        } else {
+            None
+        }
+    }
+}
+
+impl Lexer {
+    pub fn new(input: impl Into<String>) -> Self {
+        let lines = input
+            .into()
+            .lines()
+            .enumerate()
+            .map(|(i, s)| (i + 1, s.to_string()))
+            .collect::<VecDeque<_>>();
+        Self {
+            line_count: lines.len(),
+            lines,
+            line: None,
+        }
+    }
+}
+
+/// Rebuilds the filesystem from the commands run in the terminal and their
+/// output.
+pub struct Parser {
+    lexer: Lexer,
+    previous: Option<Token>,
+    current: Option<Token>,
+}
+
+impl Parser {
+    pub fn new(lexer: Lexer) -> Self {
+        Self {
+            lexer,
+            previous: None,
+            current: None,
+        }
+    }
+
+    /// An error pointing at the line of the current token, or just past the
+    /// last line at the end of input.
+    fn error(&self, expected: impl Into<String>) -> ParseError {
+        match self.lexer.line() {
+            Some((number, line)) => ParseError::new(expected, line).at(number, line, line),
+            None => ParseError::new(expected, "").at(self.lexer.line_count + 1, "", ""),
+        }
+    }
+
+    fn advance(&mut self) -> std::result::Result<(), ParseError> {
+        self.previous = self.current.clone();
+        self.current = self.lexer.next();
+        if let Some(Token::UnexpectedCharacters(_)) = self.current {
+            Err(self.error("a command or directory entry"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn consume(&mut self, token: Token) -> std::result::Result<(), ParseError> {
+        if self.current == Some(token.clone()) {
+            self.advance()?;
+        } else {
+            return Err(self.error(format!("`{}`", token)));
+        }
+        Ok(())
+    }
+
+    /// Parses the whole terminal session, which must start by listing `/`,
+    /// and returns the root directory.
+    pub fn parse(&mut self) -> std::result::Result<Directory, ParseError> {
+        self.advance()?;
+        self.consume(Token::ChangeDirectory("/".to_string()))?;
+        let root = self.parse_directory("/".to_string())?;
+        // `$ cd ..` out of the root ends its listing early.
+        if self.current.is_some() {
+            return Err(self.error("the end of input"));
+        }
+        Ok(root)
+    }
+
+    fn parse_directory(&mut self, name: String) -> std::result::Result<Directory, ParseError> {
+        self.consume(Token::ListDirectory)?;
+        let mut directory = Directory::new(name);
+        loop {
+            match self.current.clone() {
+                Some(Token::Directory(subdir_name)) => {
+                    self.advance()?;
+                    directory.add_directory(Directory::new(subdir_name));
+                }
+                Some(Token::File(ref fname, size)) => {
+                    self.advance()?;
+                    directory.add_file(fname, size);
+                }
+                Some(Token::ChangeDirectory(subdir_name)) => {
+                    let Some(subdir) = directory.get_mut_directory(subdir_name.clone()) else {
+                        return Err(self.error("a directory listed by `$ ls`"));
+                    };
+                    self.advance()?;
+                    *subdir = self.parse_directory(subdir_name)?;
+                }
+                Some(Token::ChangeToParentDirectory) => {
+                    self.advance()?;
+                    break;
+                }
+                None => {
+                    break;
+                }
+                _ => {
+                    return Err(self.error("a directory entry, `$ cd` or the end of input"));
+                }
+            }
+        }
+        Ok(directory)
+    }
+}
+
+fn find_space(
+    directory: &Directory,
+    how_much: u64,
+    candidates: &mut Vec<u64>,
+) -> std::result::Result<(), OverflowError> {
+    let size = directory.size()?;
+    if size >= how_much {
+        candidates.push(size);
+    }
+    for subdirectory in directory.directories.values() {
+        find_space(subdirectory, how_much, candidates)?;
+    }
+    Ok(())
+}
+
+fn sum_small(directory: &Directory, at_most: u64) -> std::result::Result<u64, OverflowError> {
+    let size = directory.size()?;
+    let mut sum = if size <= at_most { size } else { 0 };
+    for subdirectory in directory.directories.values() {
+        sum = sum
+            .checked_add(sum_small(subdirectory, at_most)?)
+            .ok_or_else(|| OverflowError {
+                directory: directory.name.clone(),
+            })?;
+    }
+    Ok(sum)
+}
+
+/// Reads the terminal session and returns the root directory.
+pub fn parse(input: impl BufRead) -> Result<Directory> {
+    let input = input::read_to_string(input)?;
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer);
+    Ok(parser.parse()?)
+}
+
+/// The total size of the directories of at most 100000.
+pub fn part1(root: &Directory) -> std::result::Result<u64, OverflowError> {
+    sum_small(root, 100_000)
+}
+
+/// The size of the smallest directory that frees up enough space for the
+/// update when deleted.
+pub fn part2(root: &Directory) -> std::result::Result<Option<u64>, OverflowError> {
+    let unused = 70_000_000u64.saturating_sub(root.size()?);
+    let atleast = 30_000_000u64.saturating_sub(unused);
+    let mut candidates = Vec::new();
+    find_space(root, atleast, &mut candidates)?;
+    candidates.sort();
+    Ok(candidates.first().copied())
+}
+
+/// Day 7's puzzle.
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn solve(&self, input: &mut dyn BufRead) -> Result<Answers> {
+        let root = parse(input)?;
+        let freed = part2(&root)?.ok_or_else(|| {
+            Error::Puzzle("no directory is large enough to free the space needed".into())
+        })?;
+        Ok(Answers::new(part1(&root)?, freed))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Box::new(Day7));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lexes_each_kind_of_line() {
+        let lexer = Lexer::new("$ cd /\n$ ls\ndir a\n14848514 b.txt\n\n$ cd ..\n");
+        assert_eq!(
+            lexer.collect::<Vec<_>>(),
+            vec![
+                Token::ChangeDirectory("/".to_string()),
+                Token::ListDirectory,
+                Token::Directory("a".to_string()),
+                Token::File("b.txt".to_string(), 14848514),
+                Token::ChangeToParentDirectory,
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_nested_directories() {
+        let lexer = Lexer::new("$ cd /\n$ ls\ndir a\n10 b\n$ cd a\n$ ls\n20 c\n");
+        let root = Parser::new(lexer).parse().unwrap();
+        assert_eq!(root.size(), Ok(30));
+        let a = root.directories().next().unwrap();
+        assert_eq!(a.name(), "a");
+        assert_eq!(a.files().collect::<Vec<_>>(), vec![("c", 20)]);
+    }
+
+    #[test]
+    fn parse_requires_root_first() {
+        let lexer = Lexer::new("$ ls\n10 b\n");
+        assert!(Parser::new(lexer).parse().is_err());
+    }
+
+    #[test]
+    fn parse_rejects_unexpected_lines() {
+        let lexer = Lexer::new("$ cd /\n$ ls\nnonsense\n");
+        let error = Parser::new(lexer).parse().unwrap_err();
+        assert_eq!(error.line(), Some(3));
+        assert_eq!(error.found(), "nonsense");
+    }
+
+    #[test]
+    fn parse_reports_expected_command() {
+        let lexer = Lexer::new("$ cd /\n\n$ cd a\n");
+        let error = Parser::new(lexer).parse().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected `$ ls`, found `$ cd a`\n  |\n3 | $ cd a\n  | ^^^^^^"
+        );
+    }
+
+    #[test]
+    fn parse_reports_end_of_input() {
+        let lexer = Lexer::new("$ cd /\n");
+        let error = Parser::new(lexer).parse().unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.found(), "");
+    }
+
+    #[test]
+    fn parse_rejects_lines_after_leaving_the_root() {
+        let lexer = Lexer::new("$ cd /\n$ ls\n$ cd ..\n$ ls\ngarbage here\n");
+        let error = Parser::new(lexer).parse().unwrap_err();
+        assert_eq!(error.line(), Some(4));
+        assert!(error
+            .to_string()
+            .starts_with("line 4, column 1: expected the end of input, found `$ ls`"));
+    }
+
+    #[test]
+    fn reports_sizes_that_overflow() {
+        let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n18446744073709551615 b\n1 c\n";
+        let root = parse(input.as_bytes()).unwrap();
+        let overflow = OverflowError {
+            directory: "a".to_string(),
+        };
+        assert_eq!(root.size(), Err(overflow.clone()));
+        assert_eq!(part1(&root), Err(overflow.clone()));
+        assert_eq!(part2(&root), Err(overflow));
+        let error = Day7.solve(&mut input.as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "the size of directory `a` overflows");
+    }
+
+    #[test]
+    fn lexes_bad_file_sizes_as_unexpected() {
+        let mut lexer = Lexer::new("huge b.txt\n");
+        assert_eq!(
+            lexer.next(),
+            Some(Token::UnexpectedCharacters("huge b.txt".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unlisted_directories() {
+        let lexer = Lexer::new("$ cd /\n$ ls\ndir a\n$ cd b\n");
+        let error = Parser::new(lexer).parse().unwrap_err();
+        assert_eq!(error.line(), Some(4));
+        assert_eq!(error.found(), "$ cd b");
+    }
+
+    #[test]
+    fn part2_when_disk_is_overfull() {
+        let root = Parser::new(Lexer::new("$ cd /\n$ ls\n80000000 big\n"))
+            .parse()
+            .unwrap();
+        assert_eq!(part2(&root), Ok(Some(80_000_000)));
+    }
+}
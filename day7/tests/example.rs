@@ -0,0 +1,37 @@
+use aoc_common::{Answers, Solution};
+
+const EXAMPLE: &str = include_str!("example.txt");
+
+#[test]
+fn parses_example() {
+    let root = day7::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(root.name(), "/");
+    assert_eq!(root.size(), Ok(48381165));
+    let mut sizes = root
+        .directories()
+        .map(|directory| (directory.name().to_string(), directory.size().unwrap()))
+        .collect::<Vec<_>>();
+    sizes.sort();
+    assert_eq!(
+        sizes,
+        vec![("a".to_string(), 94853), ("d".to_string(), 24933642)]
+    );
+}
+
+#[test]
+fn part1_example() {
+    let root = day7::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day7::part1(&root), Ok(95437));
+}
+
+#[test]
+fn part2_example() {
+    let root = day7::parse(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(day7::part2(&root), Ok(Some(24933642)));
+}
+
+#[test]
+fn solves_example() {
+    let answers = day7::Day7.solve(&mut EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(answers, Answers::new(95437, 24933642));
+}